@@ -0,0 +1,234 @@
+use std::{io, process};
+use std::fs::File;
+use std::fmt::Display;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use memmap2::Mmap;
+use crate::Generated;
+
+// --input <path>, - for stdin and --example N pick the input; they are taken out of the arguments
+// so the subcommands only see their own. Examples are found in the day's own fixtures directory.
+pub fn input_source(args: Vec<String>, manifest_dir: &str) -> (Vec<String>, Option<PathBuf>) {
+    let mut rest = Vec::new();
+    let mut source = Some(PathBuf::from("input.txt"));
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-" => source = None,
+            "--input" => source = match args.next() {
+                Some(path) if path == "-" => None,
+                Some(path) => Some(PathBuf::from(path)),
+                None => missing_value(&arg)
+            },
+            "--example" => source = match args.next().and_then(|example| example.parse::<u32>().ok()) {
+                Some(example) => Some(Path::new(manifest_dir).join("fixtures")
+                    .join(format!("example_{}.txt", example))),
+                None => missing_value(&arg)
+            },
+            _ => rest.push(arg)
+        }
+    }
+    (rest, source)
+}
+
+fn missing_value(option: &str) -> ! {
+    eprintln!("{} needs a value", option);
+    process::exit(1)
+}
+
+pub enum Input {
+    Mapped(Mmap),
+    Read(Vec<u8>)
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Input::Mapped(input) => input,
+            Input::Read(input) => input
+        }
+    }
+}
+
+// Files are mapped instead of read into a String, so even huge inputs are parsed in place.
+// Stdin, pipes and devices like /dev/stdin can't be mapped, so they are read.
+pub fn read_input(source: &Option<PathBuf>) -> Result<Input, String> {
+    let Some(path) = source else {
+        let mut input = Vec::new();
+        return io::stdin().read_to_end(&mut input)
+            .map(|_| Input::Read(input))
+            .map_err(|err| format!("Could not read stdin: {}", err))
+    };
+    let read_error = |err: io::Error| format!("Could not read {}: {}", path.display(), err);
+    let mut file = File::open(path).map_err(read_error)?;
+    // Safety: the input is only read, and nothing is expected to change it while a day runs.
+    match unsafe { Mmap::map(&file) } {
+        Ok(input) => Ok(Input::Mapped(input)),
+        Err(_) => {
+            let mut input = Vec::new();
+            file.read_to_end(&mut input).map(|_| Input::Read(input)).map_err(read_error)
+        }
+    }
+}
+
+pub fn exit_on_error<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1)
+    })
+}
+
+pub fn print_part<T: Display>(day: u32, json: bool, part: u32, input: &[u8], parse_time: Duration,
+                              solve: impl FnOnce() -> T) {
+    let start = Instant::now();
+    let answer = solve().to_string();
+    println!("{}", part_line(day, json, part, Ok(answer), input, parse_time, start.elapsed()));
+}
+
+// For parts some inputs have no answer to: the text output says why, and the JSON answer is null.
+pub fn print_part_or<T: Display>(day: u32, json: bool, part: u32, input: &[u8], parse_time: Duration,
+                                 missing: &str, solve: impl FnOnce() -> Option<T>) {
+    let start = Instant::now();
+    let answer = solve().map(|answer| answer.to_string()).ok_or(missing);
+    println!("{}", part_line(day, json, part, answer, input, parse_time, start.elapsed()));
+}
+
+fn part_line(day: u32, json: bool, part: u32, answer: Result<String, &str>, input: &[u8],
+             parse_time: Duration, solve_time: Duration) -> String {
+    if !json {
+        return format!("Part {}: {}", part, answer.unwrap_or_else(String::from))
+    }
+    let answer = answer.map_or_else(|_| String::from("null"), |answer| json_string(&answer));
+    format!("{{\"day\":{},\"part\":{},\"answer\":{},\"parse_time_us\":{},\"solve_time_us\":{},\"input_checksum\":\"{:016x}\"}}",
+            day, part, answer, parse_time.as_micros(), solve_time.as_micros(), checksum(input))
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            '\n' => json += "\\n",
+            '\r' => json += "\\r",
+            '\t' => json += "\\t",
+            c if c.is_control() => json += &format!("\\u{:04x}", u32::from(c)),
+            c => json.push(c)
+        }
+    }
+    json + "\""
+}
+
+// The input goes to stdout and the answers to stderr, so the input can be piped straight into a file.
+pub fn print_generated<P1: Display, P2: Display>(generated: &Generated<P1, P2>) {
+    print!("{}", generated.input);
+    if let Some(part_1) = &generated.part_1 {
+        eprintln!("Part 1: {}", part_1);
+    }
+    if let Some(part_2) = &generated.part_2 {
+        eprintln!("Part 2: {}", part_2);
+    }
+}
+
+// 64-bit FNV-1a, so dashboards and the runner's input store can tell which input an answer was computed from.
+pub fn checksum(input: &[u8]) -> u64 {
+    input.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3))
+}
+
+pub fn numeric_arg<T: FromStr>(args: &[String], i: usize, default: T) -> T {
+    args.get(i).map_or(default, |arg| arg.parse().unwrap_or_else(|_| {
+        eprintln!("argument {} should be a number: {}", i, arg);
+        process::exit(1)
+    }))
+}
+
+// TOML documents have to be a table, so the model is always wrapped in one.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Converted<T> {
+    model: T
+}
+
+// "to" writes the parsed input out as JSON or TOML, and "from" reads such a file back and writes
+// the model out as puzzle input again.
+#[cfg(feature = "serde")]
+pub fn convert<T: serde::Serialize + serde::de::DeserializeOwned>(name: &str, args: &[String],
+                                                                 parse: impl FnOnce() -> Result<T, String>,
+                                                                 to_input: impl FnOnce(&T) -> String) {
+    let format = args.get(1).map_or("", |arg| arg.as_str());
+    let converted = match args.first().map(|arg| arg.as_str()) {
+        Some("to") => parse().and_then(|model| to_format(format, &Converted { model: &model })),
+        Some("from") => std::fs::read_to_string(args.get(2).map_or("", |arg| arg.as_str()))
+            .map_err(|err| err.to_string())
+            .and_then(|text| from_format::<Converted<T>>(format, &text))
+            .map(|converted| to_input(&converted.model)),
+        _ => Err(format!("usage: {} convert <to|from> <json|toml> [path]", name))
+    };
+    print!("{}", exit_on_error(converted));
+}
+
+#[cfg(not(feature = "serde"))]
+pub fn convert<T>(_: &str, _: &[String], _: impl FnOnce() -> Result<T, String>, _: impl FnOnce(&T) -> String) {
+    eprintln!("convert needs the serde feature");
+    process::exit(1);
+}
+
+#[cfg(feature = "serde")]
+fn to_format(format: &str, converted: &impl serde::Serialize) -> Result<String, String> {
+    match format {
+        "json" => serde_json::to_string_pretty(converted).map(|json| json + "\n").map_err(|err| err.to_string()),
+        "toml" => toml::to_string(converted).map_err(|err| err.to_string()),
+        _ => Err(format!("unknown format: {}", format))
+    }
+}
+
+#[cfg(feature = "serde")]
+fn from_format<T: serde::de::DeserializeOwned>(format: &str, text: &str) -> Result<T, String> {
+    match format {
+        "json" => serde_json::from_str(text).map_err(|err| err.to_string()),
+        "toml" => toml::from_str(text).map_err(|err| err.to_string()),
+        _ => Err(format!("unknown format: {}", format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_options() {
+        let args = ["day_01", "--example", "2", "convert", "-"].map(String::from).to_vec();
+        assert_eq!((vec![String::from("day_01"), String::from("convert")], None), input_source(args, "/days/day_01"));
+        let args = ["day_01", "--example", "2"].map(String::from).to_vec();
+        assert_eq!(Some(PathBuf::from("/days/day_01/fixtures/example_2.txt")), input_source(args, "/days/day_01").1);
+        assert_eq!(0xcbf29ce484222325, checksum(b""));
+        assert_eq!(0xaf63dc4c8601ec8c, checksum(b"a"));
+    }
+
+    #[test]
+    fn json_parts() {
+        let time = Duration::from_micros(7);
+        assert_eq!("{\"day\":5,\"part\":1,\"answer\":\"35\",\"parse_time_us\":7,\"solve_time_us\":7,\"input_checksum\":\"af63dc4c8601ec8c\"}",
+                   part_line(5, true, 1, Ok(String::from("35")), b"a", time, time));
+        assert!(part_line(5, true, 2, Err("none"), b"a", time, time).contains("\"answer\":null,"));
+        assert_eq!("Part 2: none", part_line(5, false, 2, Err("none"), b"a", time, time));
+        assert_eq!("\"say \\\"hi\\\"\\\\\\n\\u0001\"", json_string("say \"hi\"\\\n\u{1}"));
+    }
+
+    #[test]
+    fn unmappable_input() {
+        let dir = std::env::temp_dir();
+        let err = read_input(&Some(dir.clone())).err().unwrap();
+        assert!(err.starts_with(&format!("Could not read {}: ", dir.display())));
+        assert!(read_input(&Some(dir.join("cli-no-such-input.txt"))).is_err());
+        let input = read_input(&Some(PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")))).unwrap();
+        assert!(input.starts_with(b"[package]"));
+        #[cfg(target_os = "linux")]
+        assert!(read_input(&Some(PathBuf::from("/dev/null"))).unwrap().is_empty());
+    }
+}
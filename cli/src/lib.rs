@@ -0,0 +1,20 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+// Running a day needs std, but the generators are part of the no_std day libraries, so only what
+// they share is kept out of the std feature.
+#[cfg(feature = "std")]
+mod command;
+
+#[cfg(feature = "std")]
+pub use command::*;
+
+use alloc::string::String;
+
+// A generated puzzle input, and its answers when the generator could work them out.
+pub struct Generated<P1 = u64, P2 = P1> {
+    pub input: String,
+    pub part_1: Option<P1>,
+    pub part_2: Option<P2>
+}
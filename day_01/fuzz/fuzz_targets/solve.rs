@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(model) = day_01::parse(input) {
+        let _ = day_01::part_1(&model);
+        let _ = day_01::part_2(&model);
+    }
+});
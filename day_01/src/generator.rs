@@ -0,0 +1,70 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use cli::Generated;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// None of these letters appear in a digit word, so noise can never complete one.
+const NOISE: &[u8] = b"abcdjklmpqyz";
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+const OVERLAPS: [(&str, u64, u64); 8] = [("oneight", 1, 8), ("twone", 2, 1), ("threeight", 3, 8),
+    ("fiveight", 5, 8), ("sevenine", 7, 9), ("eightwo", 8, 2), ("eighthree", 8, 3), ("nineight", 9, 8)];
+
+pub fn generate(seed: u64, lines: usize) -> Generated {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut input = String::new();
+    let mut part_1 = 0;
+    let mut part_2 = 0;
+    for _ in 0..lines {
+        let tokens = rng.gen_range(1..=6);
+        let numeral_at = rng.gen_range(0..tokens);
+        let mut numerals = Vec::new();
+        let mut first_digit = None;
+        let mut last_digit = 0;
+        for i in 0..tokens {
+            push_noise(&mut rng, &mut input);
+            let (token, first, last) = if i == numeral_at || rng.gen_bool(0.3) {
+                let numeral = rng.gen_range(1..=9);
+                numerals.push(numeral);
+                (numeral.to_string(), numeral, numeral)
+            } else if rng.gen_bool(0.4) {
+                let (overlap, first, last) = OVERLAPS[rng.gen_range(0..OVERLAPS.len())];
+                (String::from(overlap), first, last)
+            } else {
+                let word = rng.gen_range(0..WORDS.len());
+                (String::from(WORDS[word]), word as u64 + 1, word as u64 + 1)
+            };
+            input += &token;
+            first_digit.get_or_insert(first);
+            last_digit = last;
+        }
+        push_noise(&mut rng, &mut input);
+        input.push('\n');
+        part_1 += 10 * numerals.first().unwrap() + numerals.last().unwrap();
+        part_2 += 10 * first_digit.unwrap() + last_digit;
+    }
+    Generated { input, part_1: Some(part_1), part_2: Some(part_2) }
+}
+
+fn push_noise(rng: &mut StdRng, line: &mut String) {
+    for _ in 0..rng.gen_range(0..4) {
+        line.push(char::from(NOISE[rng.gen_range(0..NOISE.len())]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calibration_sum, calibration_sum_actual};
+
+    #[test]
+    fn generated_answers() {
+        for seed in 0..20 {
+            let generated = generate(seed, 200);
+            assert_eq!(generated.part_1, calibration_sum(&generated.input));
+            assert_eq!(generated.part_2, Some(calibration_sum_actual(&generated.input)));
+        }
+    }
+}
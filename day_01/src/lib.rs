@@ -0,0 +1,282 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod generator;
+#[cfg(test)]
+mod properties;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::vec;
+#[cfg(feature = "serde")]
+use alloc::format;
+use core::cmp;
+use parsing::parse_lines;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub fn calibration_sum(input: &str) -> Option<u64> {
+    part_1(&parse(input.as_bytes()).expect("Could not read calibration document"))
+}
+
+pub fn calibration_sum_actual(input: &str) -> u64 {
+    part_2(&parse(input.as_bytes()).expect("Could not read calibration document"))
+}
+
+// A line's digits in the order they appear, each written either as a digit or spelled out as a word.
+// Overlapping words like "twone" both count, and everything else on the line is noise.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CalibrationListing", into = "CalibrationListing"))]
+pub struct Calibration {
+    digits: Vec<LineDigit>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct LineDigit {
+    digit: u32,
+    spelled: bool
+}
+
+impl Calibration {
+    // A line may only spell out its digits, so it only has a value for part 1 if it has a numeric one.
+    pub fn value(&self) -> Option<u32> {
+        first_and_last(&self.digits, |digit| !digit.spelled)
+    }
+
+    // There is no word for zero, so only the digits 1 to 9 count in part 2.
+    pub fn value_actual(&self) -> u32 {
+        first_and_last(&self.digits, |digit| digit.digit > 0).expect("calibration without a digit")
+    }
+
+    fn new(digits: Vec<LineDigit>) -> Result<Self, String> {
+        if !digits.iter().any(|digit| digit.digit > 0) {
+            return Err(String::from("No first digit found"))
+        }
+        Ok(Self { digits })
+    }
+}
+
+fn first_and_last(digits: &[LineDigit], counts: impl Fn(&LineDigit) -> bool) -> Option<u32> {
+    let first = digits.iter().find(|digit| counts(digit))?;
+    let last = digits.iter().rfind(|digit| counts(digit))?;
+    Some(10 * first.digit + last.digit)
+}
+
+// Digits and their words are all ASCII, so lines are searched as bytes and anything else,
+// including bytes that aren't valid UTF-8, is just noise that never matches.
+pub fn parse(input: &[u8]) -> Result<Vec<Calibration>, String> {
+    let words = digits();
+    parse_lines(input, |line| Calibration::new(line_digits(line, &words)))
+}
+
+fn line_digits(line: &[u8], words: &[Digit]) -> Vec<LineDigit> {
+    (0..line.len())
+        .filter_map(|i| {
+            if line[i].is_ascii_digit() {
+                return Some(LineDigit { digit: u32::from(line[i] - b'0'), spelled: false })
+            }
+            words.iter()
+                .find(|word| line[i..].starts_with(word.word))
+                .map(|word| LineDigit { digit: word.numeric, spelled: true })
+        })
+        .collect()
+}
+
+// Each line is written back with its digits and words run together, which reads back as the same digits.
+pub fn to_input(calibrations: &[Calibration]) -> String {
+    let words = digits();
+    let mut input = String::new();
+    for calibration in calibrations {
+        for digit in &calibration.digits {
+            if digit.spelled {
+                input.extend(words[digit.digit as usize - 1].word.iter().map(|byte| char::from(*byte)));
+            } else {
+                input.push(char::from(b'0' + digit.digit as u8));
+            }
+        }
+        input.push('\n');
+    }
+    input
+}
+
+// Lines with only spelled out digits have no calibration value in part 1, so there is no answer then.
+pub fn part_1(calibrations: &[Calibration]) -> Option<u64> {
+    calibrations.iter()
+        .map(|calibration| calibration.value().map(u64::from))
+        .sum()
+}
+
+pub fn part_2(calibrations: &[Calibration]) -> u64 {
+    calibrations.iter()
+        .map(|calibration| u64::from(calibration.value_actual()))
+        .sum()
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct CalibrationListing {
+    digits: Vec<LineDigit>
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CalibrationListing> for Calibration {
+    type Error = String;
+
+    fn try_from(listing: CalibrationListing) -> Result<Self, String> {
+        if let Some(digit) = listing.digits.iter().find(|digit| digit.digit > 9 || (digit.spelled && digit.digit == 0)) {
+            return Err(format!("not a digit: {}", digit.digit))
+        }
+        Self::new(listing.digits)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Calibration> for CalibrationListing {
+    fn from(calibration: Calibration) -> Self {
+        Self { digits: calibration.digits }
+    }
+}
+
+pub fn calibration_value(line: &[u8]) -> Result<u32, String> {
+    let digits = line.iter()
+        .filter(|byte| byte.is_ascii_digit())
+        .map(|byte| u32::from(byte - b'0'))
+        .collect::<Vec<u32>>();
+    Ok(10 * digits.first().ok_or("No first digit")? + digits.last().ok_or("No last digit")?)
+}
+
+pub fn calibration_value_actual(line: &[u8]) -> Result<u32, String> {
+    let digits = digits();
+    let first_matches = matches_ordered(line, &digits, first_in_line);
+    let last_matches = matches_ordered(line, &digits, last_in_line);
+    let first_digit = first_matches.first().ok_or("No first digit found")?.0.numeric;
+    let last_digit = last_matches.last().ok_or("No last digit found")?.0.numeric;
+    Ok(first_digit * 10 + last_digit)
+}
+
+fn matches_ordered<'a>(line: &'a [u8], digits: &'a [Digit],
+                         finder: fn(&'a Digit, &'a [u8]) -> (&'a Digit, Option<usize>))
+    -> Vec<(&'a Digit, usize)> {
+    let mut matches: Vec<(&Digit, usize)> = digits.iter()
+        .map(|digit| finder(digit, line))
+        .filter(|result| result.1.is_some())
+        .map(|result| (result.0, result.1.unwrap()))
+        .collect();
+    matches.sort_by_key(|a| a.1);
+    matches
+}
+
+fn first_in_line<'a>(digit: &'a Digit, line: &'a [u8]) -> (&'a Digit, Option<usize>) {
+    let first_word = line.windows(digit.word.len()).position(|window| window == digit.word);
+    let first_numeric = line.iter().position(|byte| *byte == digit.numeric_as_byte());
+    (digit, best_match(first_word, first_numeric, cmp::min))
+}
+
+fn last_in_line<'a>(digit: &'a Digit, line: &'a [u8]) -> (&'a Digit, Option<usize>) {
+    let last_word = line.windows(digit.word.len()).rposition(|window| window == digit.word);
+    let last_numeric = line.iter().rposition(|byte| *byte == digit.numeric_as_byte());
+    (digit, best_match(last_word, last_numeric, cmp::max))
+}
+
+fn best_match(word_match: Option<usize>, numeric_match: Option<usize>,
+              comparator: fn(usize, usize) -> usize) -> Option<usize> {
+    match word_match {
+        None => numeric_match,
+        Some (i) => match numeric_match {
+            None => Some(i),
+            Some(_) => Some(comparator(word_match.unwrap(), numeric_match.unwrap()))
+        }
+    }
+}
+
+struct Digit {
+    word: &'static [u8],
+    numeric: u32
+}
+
+impl Digit {
+    fn new(word: &'static [u8], numeric: u32) -> Self {
+        Self { word, numeric }
+    }
+
+    fn numeric_as_byte(&self) -> u8 {
+        b'0' + self.numeric as u8
+    }
+}
+
+fn digits() -> Vec<Digit> {
+    vec!(Digit::new(b"one", 1),
+         Digit::new(b"two", 2),
+         Digit::new(b"three", 3),
+         Digit::new(b"four", 4),
+         Digit::new(b"five", 5),
+         Digit::new(b"six", 6),
+         Digit::new(b"seven", 7),
+         Digit::new(b"eight", 8),
+         Digit::new(b"nine", 9))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(Some(142), calibration_sum(input));
+    }
+
+    #[test]
+    fn part_2() {
+        let input = include_str!("../fixtures/example_2.txt");
+        assert_eq!(281, calibration_sum_actual(input));
+    }
+
+    #[test]
+    fn parse_once() {
+        let calibrations = parse(b"1abc2\ntreb7uchet\nxtwone3four").unwrap();
+        assert_eq!(Some(122), super::part_1(&calibrations));
+        assert_eq!(113, super::part_2(&calibrations));
+        assert!(parse(b"1abc2\nabc").is_err());
+    }
+
+    #[test]
+    fn spelled_digits_only() {
+        assert_eq!(None, calibration_sum(include_str!("../fixtures/example_2.txt")));
+        let calibrations = parse(b"1abc2\ntwo1nine\nabcone").unwrap();
+        assert_eq!(None, super::part_1(&calibrations));
+        assert_eq!(12 + 29 + 11, super::part_2(&calibrations));
+    }
+
+    #[test]
+    fn non_utf8_noise() {
+        let calibrations = parse(b"\xff1abc\xfe2\ntwo\xc3\x28nine").unwrap();
+        assert_eq!(12 + 29, super::part_2(&calibrations));
+    }
+
+    #[test]
+    fn no_digits() {
+        assert!(calibration_value(b"abc").is_err());
+        assert!(calibration_value_actual(b"abc").is_err());
+        assert!(parse(b"0abc").is_err());
+    }
+
+    #[test]
+    fn back_to_input() {
+        let calibrations = parse(b"xtwone3four\n7pqr0stu\n").unwrap();
+        assert_eq!("twoone3four\n70\n", to_input(&calibrations));
+        assert_eq!(calibrations, parse(to_input(&calibrations).as_bytes()).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_checks_digits() {
+        let read_back: Calibration = serde_json::from_str(r#"{"digits":[{"digit":7,"spelled":true}]}"#).unwrap();
+        assert_eq!(77, read_back.value_actual());
+        assert!(serde_json::from_str::<Calibration>(r#"{"digits":[{"digit":0,"spelled":false}]}"#).is_err());
+        assert!(serde_json::from_str::<Calibration>(r#"{"digits":[{"digit":12,"spelled":false}]}"#).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use proptest::prelude::*;
+use crate::{calibration_value, calibration_value_actual, parse};
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+const TOKENS: [&str; 30] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "0",
+    "o", "n", "e", "t", "w", "i", "g", "h", "x", "a", "z"];
+
+fn line() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(TOKENS.to_vec()), 1..12)
+        .prop_map(|tokens| tokens.concat())
+}
+
+fn digits_by_scanning(line: &str, words: bool) -> Vec<u32> {
+    (0..line.len())
+        .filter_map(|i| {
+            let rest = &line[i..];
+            let numeric = rest.chars().next()
+                .and_then(|c| c.to_digit(10))
+                .filter(|digit| !words || *digit > 0);
+            let word = WORDS.iter()
+                .position(|word| words && rest.starts_with(word))
+                .map(|position| position as u32 + 1);
+            numeric.or(word)
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn calibration_value_matches_scan(line in line()) {
+        let digits = digits_by_scanning(&line, false);
+        prop_assume!(!digits.is_empty());
+        prop_assert_eq!(Ok(10 * digits[0] + digits[digits.len() - 1]), calibration_value(line.as_bytes()));
+    }
+
+    #[test]
+    fn calibration_value_actual_matches_scan(line in line()) {
+        let digits = digits_by_scanning(&line, true);
+        prop_assume!(!digits.is_empty());
+        prop_assert_eq!(Ok(10 * digits[0] + digits[digits.len() - 1]), calibration_value_actual(line.as_bytes()));
+    }
+
+    #[test]
+    fn parsed_digits_match_values(line in line()) {
+        prop_assume!(calibration_value_actual(line.as_bytes()).is_ok());
+        let calibration = &parse(line.as_bytes()).unwrap()[0];
+        prop_assert_eq!(calibration_value(line.as_bytes()).ok(), calibration.value());
+        prop_assert_eq!(calibration_value_actual(line.as_bytes()), Ok(calibration.value_actual()));
+    }
+}
@@ -0,0 +1,60 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+use cli::Generated;
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::{Rng, SeedableRng};
+
+// Extra colours are chosen so that none of them contain "red", "green" or "blue".
+const COLOURS: [&str; 11] = ["red", "green", "blue", "yellow", "purple", "orange", "white", "black", "pink",
+    "cyan", "grey"];
+
+pub fn generate(seed: u64, games: usize, colours: usize) -> Generated {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let colours: Vec<String> = (0..colours)
+        .map(|i| COLOURS.get(i).map_or(format!("shade{}", i), |colour| String::from(*colour)))
+        .collect();
+    let mut input = String::new();
+    let mut part_1 = 0;
+    let mut part_2 = 0;
+    for id in 1..=games {
+        let mut mins = [0; 3];
+        let draws: Vec<String> = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let drawn = rng.gen_range(1..=colours.len());
+                let draw: Vec<(usize, u64)> = index::sample(&mut rng, colours.len(), drawn).iter()
+                    .map(|colour| (colour, rng.gen_range(1..=20)))
+                    .collect();
+                for (colour, count) in draw.iter().filter(|(colour, _)| *colour < 3) {
+                    mins[*colour] = mins[*colour].max(*count);
+                }
+                draw.iter()
+                    .map(|(colour, count)| format!("{} {}", count, colours[*colour]))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect();
+        input += &format!("Game {}: {}\n", id, draws.join("; "));
+        if mins[0] <= 12 && mins[1] <= 13 && mins[2] <= 14 {
+            part_1 += id as u64;
+        }
+        part_2 += mins.iter().product::<u64>();
+    }
+    Generated { input, part_1: Some(part_1), part_2: Some(part_2) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{possible_id_sum, power_sum};
+
+    #[test]
+    fn generated_answers() {
+        for (seed, colours) in (0..20).zip([1, 2, 3, 5, 12].into_iter().cycle()) {
+            let generated = generate(seed, 100, colours);
+            assert_eq!(generated.part_1, Some(possible_id_sum(&generated.input)));
+            assert_eq!(generated.part_2.map(u128::from), Some(power_sum(&generated.input)));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod generator;
+#[cfg(test)]
+mod properties;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+use core::cmp;
+use parsing::{column_error, key_values, labelled_id, parse_lines, parse_number, KeyValue};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub fn possible_id_sum(input: &str) -> u64 {
+    part_1(&parse(input.as_bytes()).expect("Could not read game"))
+}
+
+pub fn power_sum(input: &str) -> u128 {
+    part_2(&parse(input.as_bytes()).expect("Could not read game"))
+}
+
+pub fn parse(input: &[u8]) -> Result<Vec<GameMins>, String> {
+    parse_lines(input, game_mins_for_line)
+}
+
+pub fn part_1(games: &[GameMins]) -> u64 {
+    games.iter()
+        .filter(|game_mins| game_mins.within_maxes(12, 13, 14))
+        .map(|game_mins| u64::from(game_mins.id))
+        .sum()
+}
+
+// Each game is written back as one draw of its minimum counts, which gives the same answers.
+pub fn to_input(games: &[GameMins]) -> String {
+    games.iter()
+        .map(|game| format!("Game {}: {} red, {} green, {} blue\n", game.id, game.red_min, game.green_min, game.blue_min))
+        .collect()
+}
+
+// Three mins multiply to at most 96 bits, so neither the powers nor their sum can overflow.
+pub fn part_2(games: &[GameMins]) -> u128 {
+    games.iter()
+        .map(|game_mins| game_mins.power())
+        .sum()
+}
+
+pub fn game_mins_for_line(line: &[u8]) -> Result<GameMins, String> {
+    let (id, draws) = labelled_id(line, "Game")?;
+    let draws = draws.split(|byte| *byte == b';')
+        .map(|draw| key_values(draw, b',').map_err(|err| column_error(line, draw, err)))
+        .collect::<Result<Vec<Draw>, String>>()?;
+    let red_min = min_draws(line, &draws, "red")?;
+    let green_min = min_draws(line, &draws, "green")?;
+    let blue_min = min_draws(line, &draws, "blue")?;
+    Ok(GameMins { id, red_min, green_min, blue_min })
+}
+
+// Each draw is a list of counts and colours, like "3 blue, 4 red".
+type Draw<'a> = Vec<KeyValue<'a>>;
+
+fn min_draws(line: &[u8], draws: &[Draw], colour: &str) -> Result<u32, String> {
+    let mut min = 0;
+    for draw in draws {
+        min = cmp::max(min, number_of_colour_drawn(line, draw, colour)?);
+    }
+    Ok(min)
+}
+
+fn number_of_colour_drawn(line: &[u8], draw: &[KeyValue], colour: &str) -> Result<u32, String> {
+    draw.iter()
+        .filter(|(_, drawn_colour)| *drawn_colour == colour.as_bytes())
+        .try_fold(0_u32, |sum, (count, _)| sum.checked_add(colour_count(line, count)?)
+            .ok_or_else(|| format!("too many {} cubes drawn", colour)))
+}
+
+fn colour_count(line: &[u8], count: &[u8]) -> Result<u32, String> {
+    parse_number(count).ok_or_else(|| column_error(line, count,
+        format!("could not interpret number part as a number: {}", String::from_utf8_lossy(count))))
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameMins {
+    id: u32,
+    red_min: u32,
+    green_min: u32,
+    blue_min: u32
+}
+
+impl GameMins {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn mins(&self) -> (u32, u32, u32) {
+        (self.red_min, self.green_min, self.blue_min)
+    }
+
+    pub fn within_maxes(&self, red: u32, green: u32, blue: u32) -> bool {
+        self.red_min <= red && self.green_min <= green && self.blue_min <= blue
+    }
+
+    pub fn power(&self) -> u128 {
+        u128::from(self.red_min) * u128::from(self.green_min) * u128::from(self.blue_min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(8, possible_id_sum(input));
+    }
+
+    #[test]
+    fn part_2() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(2286, power_sum(input));
+    }
+
+    #[test]
+    fn parse_once() {
+        let games = parse(b"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red").unwrap();
+        assert_eq!(1, super::part_1(&games));
+        assert_eq!(48 + 1560, super::part_2(&games));
+        assert!(parse(b"Game 1: 3 blue\nGame x: 3 blue").is_err());
+        assert_eq!("Game 1: 4 red, 2 green, 6 blue\nGame 3: 20 red, 13 green, 6 blue\n", to_input(&games));
+    }
+
+    #[test]
+    fn malformed_game() {
+        assert!(game_mins_for_line(b"Game x: 3 blue").is_err());
+        assert_eq!(Err(String::from("column 17: expected two words: red")), game_mins_for_line(b"Game 1: 3 blue, red").map(|_| ()));
+        assert_eq!(Err(String::from("column 16: could not interpret number part as a number: x")),
+                   game_mins_for_line(b"Game 1: 3 red; x blue").map(|_| ()));
+        assert!(game_mins_for_line(b"Game 1: 4294967295 red, 1 red").is_err());
+        let game_mins = game_mins_for_line(b"Game 1: 4294967295 red, 4294967295 green, 4294967295 blue").unwrap();
+        assert_eq!(u128::from(u32::MAX).pow(3), game_mins.power());
+    }
+
+    #[test]
+    fn large_id_sum() {
+        assert_eq!(2 * u64::from(u32::MAX), possible_id_sum("Game 4294967295: 1 red\nGame 4294967295: 2 blue"));
+    }
+}
@@ -0,0 +1,66 @@
+use proptest::prelude::*;
+use crate::{game_mins_for_line, possible_id_sum, power_sum};
+
+const COLOURS: [&str; 5] = ["red", "green", "blue", "yellow", "purple"];
+
+type Draw = Vec<(u32, &'static str)>;
+
+fn game() -> impl Strategy<Value = Vec<Draw>> {
+    let draw = prop::sample::subsequence(COLOURS.to_vec(), 1..=COLOURS.len())
+        .prop_flat_map(|colours| {
+            let counts = prop::collection::vec(1..30_u32, colours.len());
+            (Just(colours), counts)
+        })
+        .prop_map(|(colours, counts)| counts.into_iter().zip(colours).collect());
+    prop::collection::vec(draw, 1..6)
+}
+
+fn game_line(id: usize, draws: &[Draw]) -> String {
+    let draws: Vec<String> = draws.iter()
+        .map(|draw| draw.iter()
+            .map(|(count, colour)| format!("{} {}", count, colour))
+            .collect::<Vec<String>>()
+            .join(", "))
+        .collect();
+    format!("Game {}: {}", id, draws.join("; "))
+}
+
+fn max_drawn(draws: &[Draw], colour: &str) -> u32 {
+    draws.iter()
+        .flatten()
+        .filter(|(_, drawn_colour)| *drawn_colour == colour)
+        .map(|(count, _)| *count)
+        .max()
+        .unwrap_or(0)
+}
+
+proptest! {
+    #[test]
+    fn game_mins_match_max_drawn(id in 1..1000_usize, draws in game()) {
+        let game_mins = game_mins_for_line(game_line(id, &draws).as_bytes()).unwrap();
+        prop_assert_eq!(id as u32, game_mins.id);
+        prop_assert_eq!(max_drawn(&draws, "red"), game_mins.red_min);
+        prop_assert_eq!(max_drawn(&draws, "green"), game_mins.green_min);
+        prop_assert_eq!(max_drawn(&draws, "blue"), game_mins.blue_min);
+    }
+
+    #[test]
+    fn sums_match_max_drawn(games in prop::collection::vec(game(), 1..10)) {
+        let input = games.iter().enumerate()
+            .map(|(i, draws)| game_line(i + 1, draws))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let maxes: Vec<[u32; 3]> = games.iter()
+            .map(|draws| [max_drawn(draws, "red"), max_drawn(draws, "green"), max_drawn(draws, "blue")])
+            .collect();
+        let expected_id_sum = maxes.iter().enumerate()
+            .filter(|(_, [red, green, blue])| *red <= 12 && *green <= 13 && *blue <= 14)
+            .map(|(i, _)| i as u64 + 1)
+            .sum::<u64>();
+        let expected_power_sum = maxes.iter()
+            .map(|maxes| maxes.iter().map(|max| u128::from(*max)).product::<u128>())
+            .sum::<u128>();
+        prop_assert_eq!(expected_id_sum, possible_id_sum(&input));
+        prop_assert_eq!(expected_power_sum, power_sum(&input));
+    }
+}
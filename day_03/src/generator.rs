@@ -0,0 +1,81 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::vec;
+use cli::Generated;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+struct PlacedNumber {
+    value: u64,
+    row: usize,
+    col: usize,
+    len: usize
+}
+
+impl PlacedNumber {
+    fn is_adjacent(&self, row: usize, col: usize) -> bool {
+        row + 1 >= self.row && row <= self.row + 1 && col + 1 >= self.col && col <= self.col + self.len
+    }
+}
+
+pub fn generate(seed: u64, size: usize, density: u32) -> Generated {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut grid = vec![vec![b'.'; size]; size];
+    let mut numbers = Vec::new();
+    let mut symbols = Vec::new();
+    for (row, grid_row) in grid.iter_mut().enumerate() {
+        let mut col = 0;
+        while col < size {
+            if rng.gen_range(0..100) >= density {
+                col += 1;
+                continue;
+            }
+            let len = rng.gen_range(1..=3);
+            if rng.gen_bool(0.7) && col + len <= size {
+                let value = rng.gen_range(10_u64.pow(len as u32 - 1)..10_u64.pow(len as u32));
+                grid_row[col..(col + len)].copy_from_slice(value.to_string().as_bytes());
+                numbers.push(PlacedNumber { value, row, col, len });
+                col += len + 1;
+            } else {
+                let symbol = SYMBOLS[rng.gen_range(0..SYMBOLS.len())];
+                grid_row[col] = symbol;
+                symbols.push((row, col, symbol));
+                col += 1;
+            }
+        }
+    }
+    let part_1 = numbers.iter()
+        .filter(|number| symbols.iter().any(|(row, col, _)| number.is_adjacent(*row, *col)))
+        .map(|number| number.value)
+        .sum();
+    let part_2 = symbols.iter()
+        .filter(|(_, _, symbol)| *symbol == b'*')
+        .map(|(row, col, _)| numbers.iter()
+            .filter(|number| number.is_adjacent(*row, *col))
+            .map(|number| number.value)
+            .collect::<Vec<u64>>())
+        .filter(|adjacent| adjacent.len() > 1)
+        .map(|adjacent| adjacent.iter().product::<u64>())
+        .sum();
+    let input = grid.iter()
+        .map(|grid_row| String::from_utf8(grid_row.clone()).unwrap() + "\n")
+        .collect();
+    Generated { input, part_1: Some(part_1), part_2: Some(part_2) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{schematic_gear_ratio_sum, schematic_part_sum};
+
+    #[test]
+    fn generated_answers() {
+        for (seed, density) in (0..20).zip([5, 15, 30].into_iter().cycle()) {
+            let generated = generate(seed, 40, density);
+            assert_eq!(generated.part_1, Some(schematic_part_sum(&generated.input)));
+            assert_eq!(generated.part_2.map(u128::from), schematic_gear_ratio_sum(&generated.input));
+        }
+    }
+}
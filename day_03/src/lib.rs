@@ -0,0 +1,384 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod generator;
+#[cfg(test)]
+mod properties;
+
+#[cfg(feature = "serde")]
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use hashbrown::{HashMap, HashSet};
+use parsing::parse_rows;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub fn schematic_part_sum(input: &str) -> u64 {
+    part_1(&parse(input.as_bytes()).expect("Could not read numbers"))
+}
+
+pub fn schematic_gear_ratio_sum(input: &str) -> Option<u128> {
+    part_2(&parse(input.as_bytes()).expect("Could not read numbers"))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SchematicListing", into = "SchematicListing"))]
+pub struct Schematic {
+    numbers: Vec<Vec<(u32, usize)>>,
+    symbols: Vec<HashSet<usize>>,
+    gears: Vec<HashSet<usize>>
+}
+
+impl Schematic {
+    pub fn numbers(&self) -> &[Vec<(u32, usize)>] {
+        &self.numbers
+    }
+
+    pub fn is_part_number(&self, row: usize, number: u32, start_pos: usize) -> bool {
+        !adjacent_positions(&self.symbols, row, number, start_pos).is_empty()
+    }
+
+    pub fn adjacent_gears(&self, row: usize, number: u32, start_pos: usize) -> Vec<(usize, usize)> {
+        adjacent_positions(&self.gears, row, number, start_pos)
+    }
+
+    pub fn gear_numbers(&self, gear_row: usize, gear_col: usize) -> Vec<u32> {
+        let from_row = gear_row.saturating_sub(1);
+        self.numbers.iter().enumerate()
+            .skip(from_row)
+            .take(gear_row + 2 - from_row)
+            .flat_map(|(_, numbers_row)| numbers_row.iter()
+                .filter(|(number, start_pos)| number_span(*number, *start_pos).contains(&gear_col))
+                .map(|(number, _)| *number))
+            .collect()
+    }
+}
+
+// A listing is only accepted if it could have come from a schematic: every gear is also a symbol,
+// and the numbers and symbols on a row don't overlap or run into each other.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SchematicListing {
+    numbers: Vec<Vec<(u32, usize)>>,
+    symbols: Vec<Vec<usize>>,
+    gears: Vec<Vec<usize>>
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SchematicListing> for Schematic {
+    type Error = String;
+
+    fn try_from(listing: SchematicListing) -> Result<Self, String> {
+        if listing.symbols.len() != listing.numbers.len() || listing.gears.len() != listing.numbers.len() {
+            return Err(String::from("numbers, symbols and gears have different numbers of rows"))
+        }
+        let symbols: Vec<HashSet<usize>> = listing.symbols.into_iter()
+            .map(|symbols_row| symbols_row.into_iter().collect())
+            .collect();
+        let gears: Vec<HashSet<usize>> = listing.gears.into_iter()
+            .map(|gears_row| gears_row.into_iter().collect())
+            .collect();
+        for (row, numbers_row) in listing.numbers.iter().enumerate() {
+            if let Some(pos) = gears[row].iter().find(|pos| !symbols[row].contains(*pos)) {
+                return Err(format!("row {}: the gear at {} is not a symbol", row + 1, pos))
+            }
+            let mut spans = numbers_row.iter()
+                .map(|(number, start_pos)| {
+                    // number_span reaches one column past the number.
+                    let len = number.to_string().len();
+                    start_pos.checked_add(len + 1).map(|_| (*start_pos, start_pos + len))
+                        .ok_or_else(|| format!("row {}: {} at {} is too far right", row + 1, number, start_pos))
+                })
+                .collect::<Result<Vec<(usize, usize)>, String>>()?;
+            spans.sort_unstable();
+            if spans.windows(2).any(|pair| pair[1].0 <= pair[0].1) {
+                return Err(format!("row {}: numbers overlap or touch", row + 1))
+            }
+            if spans.iter().any(|(start, end)| symbols[row].iter().any(|pos| (start..end).contains(&pos))) {
+                return Err(format!("row {}: a symbol is inside a number", row + 1))
+            }
+        }
+        Ok(Self { numbers: listing.numbers, symbols, gears })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Schematic> for SchematicListing {
+    fn from(schematic: Schematic) -> Self {
+        let sorted = |rows: Vec<HashSet<usize>>| rows.into_iter()
+            .map(|row| {
+                let mut row: Vec<usize> = row.into_iter().collect();
+                row.sort_unstable();
+                row
+            })
+            .collect();
+        Self { numbers: schematic.numbers, symbols: sorted(schematic.symbols), gears: sorted(schematic.gears) }
+    }
+}
+
+fn adjacent_positions(symbols: &[HashSet<usize>], row: usize, number: u32, start_pos: usize) -> Vec<(usize, usize)> {
+    let span = number_span(number, start_pos);
+    let from_row = row.saturating_sub(1);
+    symbols.iter().enumerate()
+        .skip(from_row)
+        .take(row + 2 - from_row)
+        .flat_map(|(symbol_row, symbols_row)| span.iter()
+            .filter(|pos| symbols_row.contains(*pos))
+            .map(move |pos| (symbol_row, *pos)))
+        .collect()
+}
+
+// Columns are counted in characters, so the bytes are only used as they are when every character
+// is a single byte. Any other character is a symbol, and is narrowed to one byte so it takes one column.
+pub fn parse(input: &[u8]) -> Result<Schematic, String> {
+    if !input.is_ascii() {
+        let input = core::str::from_utf8(input).map_err(|_| "schematic is not valid UTF-8")?;
+        let columns: Vec<u8> = input.chars()
+            .map(|c| if c.is_ascii() { c as u8 } else { b'#' })
+            .collect();
+        return parse(&columns)
+    }
+    Ok(Schematic {
+        numbers: parse_rows(input, numbers_from_line)?,
+        symbols: parse_rows(input, |line| Ok(symbols_from_line(line, any_symbol_matcher)))?,
+        gears: parse_rows(input, |line| Ok(symbols_from_line(line, gear_matcher)))?
+    })
+}
+
+// Gears are written back as * and every other symbol as #, which gives the same answers.
+pub fn to_input(schematic: &Schematic) -> String {
+    let mut input = String::new();
+    for (row, numbers_row) in schematic.numbers.iter().enumerate() {
+        let mut line = Vec::new();
+        let mut place = |pos: usize, text: &[u8]| {
+            if line.len() < pos + text.len() {
+                line.resize(pos + text.len(), b'.');
+            }
+            line[pos..pos + text.len()].copy_from_slice(text);
+        };
+        for (number, start_pos) in numbers_row {
+            place(*start_pos, number.to_string().as_bytes());
+        }
+        for pos in schematic.symbols.get(row).into_iter().flatten() {
+            let gear = schematic.gears.get(row).is_some_and(|gears| gears.contains(pos));
+            place(*pos, if gear { b"*" } else { b"#" });
+        }
+        input.extend(line.iter().map(|byte| char::from(*byte)));
+        input.push('\n');
+    }
+    input
+}
+
+pub fn part_1(schematic: &Schematic) -> u64 {
+    let empty_symbols_row: HashSet<usize> = HashSet::new();
+    let symbols = &schematic.symbols;
+    schematic.numbers.iter().enumerate()
+        .map(|(i, numbers_row)| adjacent_part_sum(numbers_row,
+        if i == 0 { &empty_symbols_row } else { &symbols[i-1] },
+        &symbols[i],
+        if i == symbols.len() - 1 { &empty_symbols_row } else { &symbols[i+1] }))
+        .sum()
+}
+
+// A gear can touch up to six numbers, and the product of that many can be too large even for a
+// u128, so the sum is None then.
+pub fn part_2(schematic: &Schematic) -> Option<u128> {
+    let gears_to_numbers: HashMap<(usize, usize), Vec<u32>> = schematic.numbers.iter().enumerate()
+        .flat_map(|(i, numbers_row)| gears_to_adjacent_numbers(numbers_row, i, &schematic.gears))
+        .map(|(gear_row, gear_col, number)| ((gear_row, gear_col), number))
+        .fold(HashMap::new(),
+        |the_map, entry| {
+            add_entry(the_map, entry)
+        });
+    gears_to_numbers.iter()
+        .filter(|(_, v)| v.len() > 1)
+        .try_fold(0_u128, |sum, (_, v)| {
+            let ratio = v.iter().try_fold(1_u128, |ratio, number| ratio.checked_mul(u128::from(*number)))?;
+            sum.checked_add(ratio)
+        })
+}
+
+fn add_entry(mut the_map: HashMap<(usize, usize), Vec<u32>>, entry: ((usize, usize), u32))
+    -> HashMap<(usize, usize), Vec<u32>> {
+    let numbers = the_map.entry(entry.0).or_default();
+    numbers.push(entry.1);
+    the_map
+}
+
+fn gears_to_adjacent_numbers(numbers_row: &[(u32, usize)], row: usize, gears: &[HashSet<usize>])
+    -> Vec<(usize, usize, u32)> {
+    let from_row = if row == 0 { 0 } else { row - 1};
+    let to_row = if row == gears.len() - 1 { gears.len() } else { row + 2 };
+    (from_row..to_row).collect::<Vec<usize>>().iter()
+        .map(|row_number| (row_number, gears.get(*row_number).unwrap()))
+        .flat_map(|(row_number, gears_row)| adjacent_numbers(gears_row, numbers_row, *row_number))
+        .collect()
+}
+
+fn adjacent_numbers(gears_row: &HashSet<usize>, numbers_row: &[(u32, usize)], row_number: usize)
+-> Vec<(usize, usize, u32)> {
+    numbers_row.iter()
+        .map(|(number, start_pos)| (number, number_span(*number, *start_pos)))
+        .map(|(number, pos_s)| (number, filter_gear_hits(&pos_s, gears_row)))
+        .flat_map(|(number, gear_hits)| create_hit_entries(*number, &gear_hits, row_number))
+        .collect()
+}
+
+fn create_hit_entries(number: u32, gear_hits: &[usize], row_number: usize) -> Vec<(usize, usize, u32)> {
+    gear_hits.iter()
+        .map(|pos| (row_number, *pos, number))
+        .collect()
+}
+
+fn filter_gear_hits(pos_s: &[usize], gears_row: &HashSet<usize>) -> Vec<usize> {
+    pos_s.iter()
+        .filter(|pos| gears_row.contains(*pos))
+        .copied()
+        .collect()
+}
+
+fn number_span(number: u32, start_pos: usize) -> Vec<usize> {
+    let number_len = number.to_string().len();
+    let from_pos = if start_pos == 0 { 0 } else { start_pos - 1 };
+    let to_pos = start_pos + number_len + 1;
+    (from_pos..to_pos).collect::<Vec<usize>>()
+}
+
+fn symbols_from_line(input: &[u8], symbol_matcher: fn(&u8) -> bool) -> HashSet<usize> {
+    input.iter().enumerate()
+        .filter(|(_, c)| symbol_matcher(c))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn any_symbol_matcher(c: &u8) -> bool {
+    !(c.is_ascii_digit() || c.eq(&b'.'))
+}
+
+fn gear_matcher(c: &u8) -> bool {
+    c.eq(&b'*')
+}
+
+pub fn numbers_from_line(input: &[u8]) -> Result<Vec<(u32, usize)>, String> {
+    let mut numbers = Vec::new();
+    let mut cur_number: Option<u32> = None;
+    for (i, c) in input.iter()
+        .copied()
+        .chain([b'.'])
+        .enumerate() {
+        if c.is_ascii_digit() {
+            let c_digit = u32::from(c - b'0');
+            if cur_number.is_none() {
+                cur_number = Some(c_digit);
+            } else {
+                cur_number = Some(cur_number.unwrap().checked_mul(10)
+                    .and_then(|number| number.checked_add(c_digit))
+                    .ok_or("number too large")?);
+            }
+        } else if cur_number.is_some() {
+            let cur_number_unwrapped = cur_number.unwrap();
+            numbers.push((cur_number_unwrapped,
+                          i - cur_number_unwrapped.to_string().len()));
+            cur_number = None;
+        }
+    };
+    Ok(numbers)
+}
+
+fn adjacent_part_sum(numbers_row: &[(u32, usize)], prev_symbols_row: &HashSet<usize>,
+                       same_symbols_row: &HashSet<usize>, next_symbols_row: &HashSet<usize>) -> u64 {
+    numbers_row.iter()
+        .filter(|(number, start_pos)| is_adjacent(number, start_pos,
+                                                  prev_symbols_row, same_symbols_row, next_symbols_row))
+        .map(|(number, _)| u64::from(*number))
+        .sum()
+}
+
+fn is_adjacent(number: &u32, start_pos: &usize, prev_symbols_row: &HashSet<usize>,
+                 same_symbols_row: &HashSet<usize>, next_symbols_row: &HashSet<usize>) -> bool {
+    number_span(*number, *start_pos).iter()
+        .any(|pos| prev_symbols_row.contains(pos)
+            || same_symbols_row.contains(pos)
+            || next_symbols_row.contains(pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(4361, schematic_part_sum(input));
+    }
+
+    #[test]
+    fn part_2() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(Some(467835), schematic_gear_ratio_sum(input));
+    }
+
+    #[test]
+    fn parse_once() {
+        let schematic = parse(b"467..114..\n...*......\n..35..633.").unwrap();
+        assert_eq!(502, super::part_1(&schematic));
+        assert_eq!(Some(16345), super::part_2(&schematic));
+        assert!(parse(b"...*......\n.4294967296*").is_err());
+        assert_eq!("467..114\n...*\n..35..633\n", to_input(&schematic));
+        assert_eq!(Some(16345), super::part_2(&parse(to_input(&schematic).as_bytes()).unwrap()));
+    }
+
+    #[test]
+    fn adjacency() {
+        let schematic = parse(b"467..114..\n...*......\n..35..633.").unwrap();
+        assert!(schematic.is_part_number(0, 467, 0));
+        assert!(!schematic.is_part_number(0, 114, 5));
+        assert_eq!(vec![(1, 3)], schematic.adjacent_gears(2, 35, 2));
+        assert_eq!(vec![467, 35], schematic.gear_numbers(1, 3));
+    }
+
+    #[test]
+    fn non_ascii_symbols() {
+        let schematic = parse("467..114..\n...°......\n..35..633.".as_bytes()).unwrap();
+        assert_eq!(502, super::part_1(&schematic));
+        assert!(parse(b"467..114..\n...\xff......").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_checks_listing() {
+        let schematic = parse(b"467..114..\n...*..#...\n..35..633.").unwrap();
+        let json = serde_json::to_string(&schematic).unwrap();
+        assert_eq!(r#"{"numbers":[[[467,0],[114,5]],[],[[35,2],[633,6]]],"symbols":[[],[3,6],[]],"gears":[[],[3],[]]}"#, json);
+        assert_eq!(schematic, serde_json::from_str(&json).unwrap());
+        let rejected = |json: &str| serde_json::from_str::<Schematic>(json).err().unwrap().to_string();
+        assert!(rejected(r#"{"numbers":[[[1,18446744073709551615]]],"symbols":[[]],"gears":[[]]}"#)
+            .starts_with("row 1: 1 at 18446744073709551615 is too far right"));
+        assert!(rejected(r#"{"numbers":[[[1,0]],[]],"symbols":[[]],"gears":[[]]}"#)
+            .starts_with("numbers, symbols and gears have different numbers of rows"));
+        assert!(rejected(r#"{"numbers":[[[1,0]]],"symbols":[[]],"gears":[[1]]}"#)
+            .starts_with("row 1: the gear at 1 is not a symbol"));
+        assert!(rejected(r#"{"numbers":[[[12,0],[3,2]]],"symbols":[[]],"gears":[[]]}"#)
+            .starts_with("row 1: numbers overlap or touch"));
+        assert!(rejected(r#"{"numbers":[[[12,0]]],"symbols":[[1]],"gears":[[]]}"#)
+            .starts_with("row 1: a symbol is inside a number"));
+    }
+
+    #[test]
+    fn number_too_large() {
+        assert_eq!(Ok(vec![(4294967295, 1)]), numbers_from_line(b".4294967295*"));
+        assert!(numbers_from_line(b".4294967296*").is_err());
+    }
+
+    #[test]
+    fn large_answers() {
+        assert_eq!(Some(99999 * 99999), schematic_gear_ratio_sum("99999*99999"));
+        assert_eq!(2 * 4294967295, schematic_part_sum("4294967295*4294967295"));
+        let ratio = u128::from(u32::MAX).pow(4);
+        assert_eq!(Some(ratio), schematic_gear_ratio_sum("4294967295.4294967295\n..........*..........\n4294967295.4294967295"));
+        assert_eq!(None, schematic_gear_ratio_sum("4294967295.4294967295\n4294967295*4294967295\n4294967295.4294967295"));
+    }
+}
@@ -0,0 +1,78 @@
+use proptest::prelude::*;
+use crate::{schematic_gear_ratio_sum, schematic_part_sum};
+
+const CELLS: [char; 16] = ['.', '.', '.', '.', '.', '.', '1', '2', '5', '7', '9', '0', '*', '*', '#', '+'];
+
+// Like the puzzle input, numbers never start with a zero.
+fn schematic() -> impl Strategy<Value = Vec<Vec<char>>> {
+    (1..9_usize, 1..9_usize)
+        .prop_flat_map(|(width, height)| prop::collection::vec(
+            prop::collection::vec(prop::sample::select(CELLS.to_vec()), width), height))
+        .prop_map(|mut grid| {
+            for cells in grid.iter_mut() {
+                for col in 0..cells.len() {
+                    if cells[col] == '0' && (col == 0 || !cells[col - 1].is_ascii_digit()) {
+                        cells[col] = '.';
+                    }
+                }
+            }
+            grid
+        })
+}
+
+// Every number as (value, row, first column, last column), found by walking the grid cell by cell.
+fn numbers_by_scanning(grid: &[Vec<char>]) -> Vec<(u64, usize, usize, usize)> {
+    let mut numbers = Vec::new();
+    for (row, cells) in grid.iter().enumerate() {
+        let mut col = 0;
+        while col < cells.len() {
+            let len = cells[col..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if len > 0 {
+                let value = cells[col..(col + len)].iter().collect::<String>().parse().unwrap();
+                numbers.push((value, row, col, col + len - 1));
+            }
+            col += len.max(1);
+        }
+    }
+    numbers
+}
+
+fn is_adjacent(number: &(u64, usize, usize, usize), row: usize, col: usize) -> bool {
+    let (_, number_row, first_col, last_col) = *number;
+    row + 1 >= number_row && row <= number_row + 1 && col + 1 >= first_col && col <= last_col + 1
+}
+
+fn cells_matching(grid: &[Vec<char>], matcher: fn(char) -> bool) -> Vec<(usize, usize)> {
+    grid.iter().enumerate()
+        .flat_map(|(row, cells)| cells.iter().enumerate()
+            .filter(|(_, c)| matcher(**c))
+            .map(move |(col, _)| (row, col)))
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn sums_match_neighbour_scan(grid in schematic()) {
+        let input = grid.iter()
+            .map(|cells| cells.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n");
+        let numbers = numbers_by_scanning(&grid);
+        let symbols = cells_matching(&grid, |c| c != '.' && !c.is_ascii_digit());
+        let gears = cells_matching(&grid, |c| c == '*');
+        let expected_part_sum: u64 = numbers.iter()
+            .filter(|number| symbols.iter().any(|(row, col)| is_adjacent(number, *row, *col)))
+            .map(|(value, _, _, _)| value)
+            .sum();
+        let expected_gear_ratio_sum = gears.iter()
+            .map(|(row, col)| numbers.iter()
+                .filter(|number| is_adjacent(number, *row, *col))
+                .map(|(value, _, _, _)| *value)
+                .collect::<Vec<u64>>())
+            .filter(|adjacent| adjacent.len() > 1)
+            .try_fold(0_u128, |sum, adjacent| sum.checked_add(adjacent.iter()
+                .try_fold(1_u128, |ratio, value| ratio.checked_mul(u128::from(*value)))?));
+        prop_assert_eq!(expected_part_sum, schematic_part_sum(&input));
+        prop_assert_eq!(expected_gear_ratio_sum, schematic_gear_ratio_sum(&input));
+    }
+}
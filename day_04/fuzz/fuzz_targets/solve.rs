@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(model) = day_04::parse(input) {
+        let _ = day_04::part_1(&model);
+        let _ = day_04::part_2(&model);
+    }
+});
@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|numbers: &str| {
+    let _ = day_04::trim_and_parse_numbers(numbers);
+});
@@ -0,0 +1,23 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use day_04::{generator, line_wins, line_wins_hashset};
+
+pub fn bench_line_wins(cards: usize) -> String {
+    let deck = generator::generate(0, cards, 10).input;
+    let (hashset_wins, hashset_time) = time_line_wins(&deck, line_wins_hashset);
+    let (bitset_wins, bitset_time) = time_line_wins(&deck, line_wins);
+    assert_eq!(hashset_wins, bitset_wins, "matchers disagree on the synthetic deck");
+    format!("{} cards, {} wins\n\
+             hashset: {:>10.2?}\n\
+             bitset:  {:>10.2?} ({:.1}x)\n",
+            cards, bitset_wins, hashset_time, bitset_time,
+            hashset_time.as_secs_f64() / bitset_time.as_secs_f64())
+}
+
+fn time_line_wins(deck: &str, matcher: fn(&str) -> u32) -> (u64, Duration) {
+    let start = Instant::now();
+    let wins = deck.lines()
+        .map(|line| u64::from(matcher(black_box(line))))
+        .sum();
+    (wins, start.elapsed())
+}
@@ -0,0 +1,55 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use cli::Generated;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+// Wins per card are drawn uniformly from 0..=max_wins, but like the real puzzle input no card
+// ever wins copies of cards past the end of the deck. Copies grow exponentially with the number
+// of wins, so the part 2 answer is left out if it would overflow.
+pub fn generate(seed: u64, cards: usize, max_wins: usize) -> Generated<u64, u128> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let max_wins = max_wins.min(10);
+    let mut numbers: Vec<u32> = (1..100).collect();
+    let mut input = String::new();
+    let mut part_1 = 0;
+    let mut copies = vec![Some(1_u128); cards];
+    for card in 0..cards {
+        let wins = rng.gen_range(0..=max_wins).min(cards - card - 1);
+        let (chosen, _) = numbers.partial_shuffle(&mut rng, 35);
+        let mut my_numbers = chosen[(10 - wins)..(35 - wins)].to_vec();
+        my_numbers.shuffle(&mut rng);
+        input += &format!("Card {:>3}:{} |{}\n", card + 1, number_list(&chosen[..10]), number_list(&my_numbers));
+        if wins > 0 {
+            part_1 += 1 << (wins - 1);
+        }
+        for won in (card + 1)..(card + 1 + wins) {
+            copies[won] = copies[won].zip(copies[card]).and_then(|(won, card)| won.checked_add(card));
+        }
+    }
+    let part_2 = copies.iter().try_fold(0_u128, |sum, copies| sum.checked_add((*copies)?));
+    Generated { input, part_1: Some(part_1), part_2 }
+}
+
+fn number_list(numbers: &[u32]) -> String {
+    numbers.iter()
+        .map(|number| format!(" {:>2}", number))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{card_copies_sum, card_points_sum};
+
+    #[test]
+    fn generated_answers() {
+        for (seed, max_wins) in (0..20).zip([0, 3, 10].into_iter().cycle()) {
+            let generated = generate(seed, 30, max_wins);
+            assert_eq!(generated.part_1.map(u128::from), Some(card_points_sum(&generated.input)));
+            assert_eq!(generated.part_2, card_copies_sum(&generated.input));
+        }
+    }
+}
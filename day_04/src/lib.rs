@@ -0,0 +1,269 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod generator;
+#[cfg(test)]
+mod properties;
+pub mod rules;
+pub mod trace;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format;
+use core::fmt;
+use hashbrown::HashSet;
+use parsing::{column_error, labelled_id, parse_lines, split_once};
+use rules::ScoringRules;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub fn card_copies_sum(input: &str) -> Option<u128> {
+    part_2(&card_wins(input))
+}
+
+pub fn card_points_sum(input: &str) -> u128 {
+    part_1(&card_wins(input))
+}
+
+pub fn card_wins(input: &str) -> Vec<u32> {
+    parse(input.as_bytes()).expect("Could not read card")
+}
+
+pub fn parse(input: &[u8]) -> Result<Vec<u32>, String> {
+    parse_lines(input, |line| card(line).map(|card| card.wins()))
+}
+
+// The cards themselves, for when they are kept rather than just counted.
+pub fn parse_cards(input: &[u8]) -> Result<Vec<Card>, String> {
+    parse_lines(input, card)
+}
+
+pub fn to_input(cards: &[Card]) -> String {
+    cards.iter()
+        .map(|card| format!("Card {}: {} | {}\n", card.id, card.winning, card.mine))
+        .collect()
+}
+
+pub fn part_1(card_wins: &[u32]) -> u128 {
+    ScoringRules::default().points_sum(card_wins)
+}
+
+pub fn part_2(card_wins: &[u32]) -> Option<u128> {
+    ScoringRules::default().copies_sum(card_wins)
+}
+
+pub fn line_wins(line: &str) -> u32 {
+    let (winning_numbers, my_numbers) = card_numbers(line.as_bytes()).expect("Could not read card");
+    winning_numbers.matches(&my_numbers)
+}
+
+pub fn card_numbers(line: &[u8]) -> Result<(NumberSet, NumberSet), String> {
+    card(line).map(|card| (card.winning, card.mine))
+}
+
+fn card(line: &[u8]) -> Result<Card, String> {
+    let (id, numbers) = labelled_id(line, "Card")?;
+    let (winning_numbers, my_numbers) = split_once(numbers, b'|')
+        .ok_or("no | between the winning numbers and mine")?;
+    let set = |numbers| number_set(numbers).map_err(|err| column_error(line, numbers, err));
+    Ok(Card { id, winning: set(winning_numbers)?, mine: set(my_numbers)? })
+}
+
+// The numbers are kept as sets, so they are read back in ascending order with any repeats dropped.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CardListing", into = "CardListing"))]
+pub struct Card {
+    id: u32,
+    winning: NumberSet,
+    mine: NumberSet
+}
+
+impl Card {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn wins(&self) -> u32 {
+        self.winning.matches(&self.mine)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct CardListing {
+    id: u32,
+    winning: Vec<u32>,
+    mine: Vec<u32>
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CardListing> for Card {
+    type Error = String;
+
+    fn try_from(listing: CardListing) -> Result<Self, String> {
+        let set = |numbers: &[u32]| numbers.iter().try_fold(NumberSet::default(), |mut set, number| {
+            if *number >= NumberSet::CAPACITY {
+                return Err(String::from("number too large for a scratchcard"))
+            }
+            set.insert(*number);
+            Ok(set)
+        });
+        Ok(Self { id: listing.id, winning: set(&listing.winning)?, mine: set(&listing.mine)? })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Card> for CardListing {
+    fn from(card: Card) -> Self {
+        Self { id: card.id, winning: card.winning.numbers().collect(), mine: card.mine.numbers().collect() }
+    }
+}
+
+// The digits are read straight into the set, so a card is read without allocating.
+fn number_set(numbers: &[u8]) -> Result<NumberSet, String> {
+    let mut set = NumberSet::default();
+    let mut cur_number: Option<u32> = None;
+    let mut start = 0;
+    for (i, c) in numbers.iter().copied().chain([b' ']).enumerate() {
+        if c.is_ascii_digit() {
+            if cur_number.is_none() {
+                start = i;
+            }
+            let number = cur_number.unwrap_or(0) * 10 + u32::from(c - b'0');
+            if number >= NumberSet::CAPACITY {
+                return Err(column_error(numbers, &numbers[start..], String::from("number too large for a scratchcard")))
+            }
+            cur_number = Some(number);
+        } else if c.is_ascii_whitespace() {
+            if let Some(number) = cur_number.take() {
+                set.insert(number);
+            }
+        } else {
+            return Err(column_error(numbers, &numbers[i..],
+                                    format!("not a list of numbers: {}", String::from_utf8_lossy(numbers.trim_ascii()))))
+        }
+    }
+    Ok(set)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NumberSet(u128);
+
+impl NumberSet {
+    const CAPACITY: u32 = u128::BITS;
+
+    fn insert(&mut self, number: u32) {
+        self.0 |= 1 << number;
+    }
+
+    fn matches(&self, other: &NumberSet) -> u32 {
+        (self.0 & other.0).count_ones()
+    }
+
+    fn numbers(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..Self::CAPACITY).filter(|number| self.0 & (1 << number) != 0)
+    }
+}
+
+impl fmt::Display for NumberSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let numbers: Vec<String> = self.numbers().map(|number| number.to_string()).collect();
+        write!(f, "{}", numbers.join(" "))
+    }
+}
+
+pub fn line_wins_hashset(line: &str) -> u32 {
+    let parts: Vec<&str> = line.split("|").collect();
+    let winning_numbers = winning_numbers(parts.first().expect("no winning numbers")).expect("Could not read card");
+    let my_numbers = my_numbers(parts.last().expect("no my numbers")).expect("Could not read card");
+    let wins = my_numbers.iter()
+        .filter(|num| winning_numbers.contains(*num))
+        .count();
+    u32::try_from(wins).unwrap()
+}
+
+fn winning_numbers(winning_numbers_str: &str) -> Result<HashSet<u32>, String> {
+    let parts = winning_numbers_str.split(":").collect::<Vec<&str>>();
+    Ok(trim_and_parse_numbers(parts.last().ok_or("no numbers part")?)?.iter()
+        .copied()
+        .collect())
+}
+
+fn my_numbers(my_numbers_str: &str) -> Result<Vec<u32>, String> {
+    trim_and_parse_numbers(my_numbers_str)
+}
+
+pub fn trim_and_parse_numbers(numbers_str: &str) -> Result<Vec<u32>, String> {
+    numbers_str.split(" ").collect::<Vec<&str>>().iter()
+        .map(|num| num.trim())
+        .filter(|num| !num.is_empty())
+        .map(|num| num.parse().map_err(|_| format!("number was not a number: {}", num)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(13, card_points_sum(input));
+    }
+
+    #[test]
+    fn part_2() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(Some(30), card_copies_sum(input));
+    }
+
+    #[test]
+    fn line_wins_bitset() {
+        let lines = ["Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+            "Card 7: 0 99 127 | 127 0 98"];
+        for line in lines {
+            assert_eq!(line_wins_hashset(line), line_wins(line));
+        }
+    }
+
+    #[test]
+    fn parse_once() {
+        let card_wins = parse(b"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19").unwrap();
+        assert_eq!(vec![4, 2], card_wins);
+        assert_eq!(10, super::part_1(&card_wins));
+        assert_eq!(Some(3), super::part_2(&card_wins));
+        assert!(parse(b"Card 1: 41 | 83\nCard 2: 41 128 | 83").is_err());
+        assert_eq!(Ok(vec![1, 0]), parse(b"Card 1: 41 | 41\n\nCard 2: 1 | 2\n\n"));
+    }
+
+    #[test]
+    fn malformed_card() {
+        assert_eq!(Err(String::from("column 12: number too large for a scratchcard")),
+                   card_numbers(b"Card 1: 41 128 | 83 86"));
+        assert_eq!(Err(String::from("column 20: not a list of numbers: 83 8x")), card_numbers(b"Card 1: 41 4 | 83 8x"));
+        assert!(trim_and_parse_numbers("41 4x 83").is_err());
+    }
+
+    #[test]
+    fn back_to_input() {
+        let cards = parse_cards(b"Card 1: 41 48 17 | 83 86  6 17 48 48\nCard  2: 0 | 127\n").unwrap();
+        assert_eq!(vec![2, 0], cards.iter().map(Card::wins).collect::<Vec<u32>>());
+        assert_eq!("Card 1: 17 41 48 | 6 17 48 83 86\nCard 2: 0 | 127\n", to_input(&cards));
+        assert_eq!(cards, parse_cards(to_input(&cards).as_bytes()).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_lists_numbers() {
+        let card = &parse_cards(b"Card 3: 41 127 | 127 5").unwrap()[0];
+        let json = serde_json::to_string(card).unwrap();
+        assert_eq!(r#"{"id":3,"winning":[41,127],"mine":[5,127]}"#, json);
+        assert_eq!(card, &serde_json::from_str::<Card>(&json).unwrap());
+        assert!(serde_json::from_str::<Card>(r#"{"id":3,"winning":[128],"mine":[]}"#).is_err());
+    }
+}
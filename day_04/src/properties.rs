@@ -0,0 +1,47 @@
+use proptest::prelude::*;
+use crate::{line_wins, line_wins_hashset};
+use crate::rules::ScoringRules;
+
+// Winning numbers are numbers[..to] and my numbers are numbers[from..], so the two share numbers[from..to].
+fn card() -> impl Strategy<Value = (Vec<u32>, Vec<u32>)> {
+    prop::sample::subsequence((0..100).collect::<Vec<u32>>(), 0..40)
+        .prop_shuffle()
+        .prop_flat_map(|numbers| {
+            let len = numbers.len();
+            (Just(numbers), 0..=len, 0..=len)
+        })
+        .prop_map(|(numbers, from, to)| (numbers[..from.max(to)].to_vec(), numbers[from.min(to)..].to_vec()))
+}
+
+fn card_line(id: usize, winning_numbers: &[u32], my_numbers: &[u32]) -> String {
+    let numbers = |numbers: &[u32]| numbers.iter()
+        .map(|number| format!("{:>2}", number))
+        .collect::<Vec<String>>()
+        .join(" ");
+    format!("Card {:>3}: {} | {}", id, numbers(winning_numbers), numbers(my_numbers))
+}
+
+// Plays the cascade out literally: every copy of every card is processed on its own.
+fn copies_by_simulation(card_wins: &[u32]) -> u128 {
+    let mut pending: Vec<usize> = (0..card_wins.len()).collect();
+    let mut copies = 0;
+    while let Some(card) = pending.pop() {
+        copies += 1;
+        let won_to = (card + 1 + card_wins[card] as usize).min(card_wins.len());
+        pending.extend((card + 1)..won_to);
+    }
+    copies
+}
+
+proptest! {
+    #[test]
+    fn bitset_matches_hashset((winning_numbers, my_numbers) in card()) {
+        let line = card_line(1, &winning_numbers, &my_numbers);
+        prop_assert_eq!(line_wins_hashset(&line), line_wins(&line));
+    }
+
+    #[test]
+    fn copies_match_simulation(card_wins in prop::collection::vec(0..5_u32, 1..12)) {
+        prop_assert_eq!(Some(copies_by_simulation(&card_wins)), ScoringRules::default().copies_sum(&card_wins));
+    }
+}
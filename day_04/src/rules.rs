@@ -0,0 +1,138 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+use core::cmp;
+use core::str::FromStr;
+use crate::trace::card_copies_trace;
+
+pub struct ScoringRules {
+    pub points: PointRule,
+    pub copies: CopyRule
+}
+
+impl ScoringRules {
+    pub fn points_sum(&self, card_wins: &[u32]) -> u128 {
+        card_wins.iter()
+            .map(|wins| self.points.points(*wins))
+            .fold(0, u128::saturating_add)
+    }
+
+    pub fn copies_sum(&self, card_wins: &[u32]) -> Option<u128> {
+        card_copies_trace(card_wins, &self.copies).total_copies()
+    }
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self { points: PointRule::Doubling, copies: CopyRule::Below }
+    }
+}
+
+pub enum PointRule {
+    Doubling,
+    Linear,
+    Fibonacci
+}
+
+// A card matches at most 128 numbers, which every rule scores within a u128. Larger win counts saturate.
+impl PointRule {
+    pub fn points(&self, wins: u32) -> u128 {
+        if wins == 0 {
+            return 0
+        }
+        match self {
+            PointRule::Doubling => 2_u128.checked_pow(wins - 1).unwrap_or(u128::MAX),
+            PointRule::Linear => u128::from(wins),
+            PointRule::Fibonacci => {
+                let (mut prev, mut cur) = (1_u128, 1_u128);
+                for _ in 1..wins {
+                    (prev, cur) = (cur, prev.saturating_add(cur));
+                }
+                cur
+            }
+        }
+    }
+}
+
+impl FromStr for PointRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "doubling" => Ok(PointRule::Doubling),
+            "linear" => Ok(PointRule::Linear),
+            "fibonacci" => Ok(PointRule::Fibonacci),
+            _ => Err(format!("unknown point rule: {}", s))
+        }
+    }
+}
+
+// Copies are handed out in a single pass from the top of the deck, so copies that wrap
+// round onto a card that has already been processed are counted but don't win any more.
+pub enum CopyRule {
+    Below,
+    CappedBelow(u32),
+    WrapAround
+}
+
+impl CopyRule {
+    pub fn won_cards(&self, card: usize, wins: u32, deck_len: usize) -> Vec<usize> {
+        let wins = usize::try_from(wins).unwrap();
+        match self {
+            CopyRule::Below => ((card + 1)..cmp::min(card + 1 + wins, deck_len)).collect(),
+            CopyRule::CappedBelow(cap) => {
+                let wins = cmp::min(wins, usize::try_from(*cap).unwrap());
+                ((card + 1)..cmp::min(card + 1 + wins, deck_len)).collect()
+            },
+            CopyRule::WrapAround => (1..=cmp::min(wins, deck_len - 1))
+                .map(|offset| (card + offset) % deck_len)
+                .collect()
+        }
+    }
+}
+
+impl FromStr for CopyRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(":") {
+            None if s == "below" => Ok(CopyRule::Below),
+            None if s == "wrap" => Ok(CopyRule::WrapAround),
+            Some(("capped", cap)) => cap.parse()
+                .map(CopyRule::CappedBelow)
+                .map_err(|_| format!("copy cap was not a number: {}", cap)),
+            _ => Err(format!("unknown copy rule: {}", s))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_WINS: [u32; 6] = [4, 2, 2, 1, 0, 0];
+
+    #[test]
+    fn point_rules() {
+        let points_sum = |points| ScoringRules { points, copies: CopyRule::Below }
+            .points_sum(&EXAMPLE_WINS);
+        assert_eq!(13, points_sum(PointRule::Doubling));
+        assert_eq!(9, points_sum(PointRule::Linear));
+        assert_eq!(10, points_sum(PointRule::Fibonacci));
+        assert_eq!(1 << 127, PointRule::Doubling.points(128));
+        assert_eq!(407305795904080553832073954, PointRule::Fibonacci.points(128));
+        assert_eq!(u128::MAX, PointRule::Doubling.points(129));
+        assert_eq!(u128::MAX, PointRule::Fibonacci.points(200));
+    }
+
+    #[test]
+    fn copy_rules() {
+        let copies_sum = |copies, card_wins: &[u32]| ScoringRules { points: PointRule::Doubling, copies }
+            .copies_sum(card_wins);
+        assert_eq!(Some(30), copies_sum(CopyRule::Below, &EXAMPLE_WINS));
+        assert_eq!(Some(16), copies_sum(CopyRule::CappedBelow(1), &EXAMPLE_WINS));
+        assert_eq!(Some(30), copies_sum(CopyRule::WrapAround, &EXAMPLE_WINS));
+        assert_eq!(Some(3), copies_sum(CopyRule::Below, &[0, 0, 2]));
+        assert_eq!(Some(5), copies_sum(CopyRule::WrapAround, &[0, 0, 2]));
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format;
+use crate::rules::CopyRule;
+
+pub struct CascadeTrace {
+    cards: Vec<CardTrace>
+}
+
+// Copy counts grow exponentially down the deck, so a count that doesn't fit in a u128 is None.
+struct CardTrace {
+    copies: Option<u128>,
+    received: Vec<(usize, Option<u128>)>
+}
+
+pub fn card_copies_trace(card_wins: &[u32], copy_rule: &CopyRule) -> CascadeTrace {
+    let mut cards: Vec<CardTrace> = card_wins.iter()
+        .map(|_| CardTrace { copies: Some(1), received: Vec::new() })
+        .collect();
+    for (i, wins) in card_wins.iter().enumerate() {
+        let copies = cards[i].copies;
+        for j in copy_rule.won_cards(i, *wins, cards.len()) {
+            cards[j].copies = cards[j].copies.zip(copies).and_then(|(copies, won)| copies.checked_add(won));
+            cards[j].received.push((i, copies));
+        }
+    }
+    CascadeTrace { cards }
+}
+
+impl CascadeTrace {
+    pub fn copies(&self, card: usize) -> Option<u128> {
+        self.cards[card].copies
+    }
+
+    pub fn received(&self, card: usize) -> &[(usize, Option<u128>)] {
+        &self.cards[card].received
+    }
+
+    pub fn total_copies(&self) -> Option<u128> {
+        self.cards.iter()
+            .try_fold(0_u128, |total, card| total.checked_add(card.copies?))
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut table = format!("{:<8} {:>10}  {}\n", "Card", "Copies", "Received from");
+        for (i, card) in self.cards.iter().enumerate() {
+            let received = card.received.iter()
+                .map(|(from, copies)| format!("Card {} ({})", from + 1, count(*copies)))
+                .collect::<Vec<String>>()
+                .join(", ");
+            table += &format!("{:<8} {:>10}  original (1){}{}\n", format!("Card {}", i + 1), count(card.copies),
+                              if received.is_empty() { "" } else { ", " }, received);
+        }
+        table += &format!("{:<8} {:>10}\n", "Total", count(self.total_copies()));
+        table
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph cascade {\n");
+        for (i, card) in self.cards.iter().enumerate() {
+            dot += &format!("    card_{} [label=\"Card {}\\n{} copies\"];\n", i + 1, i + 1, count(card.copies));
+        }
+        for (i, card) in self.cards.iter().enumerate() {
+            for (from, copies) in &card.received {
+                dot += &format!("    card_{} -> card_{} [label=\"{}\"];\n", from + 1, i + 1, count(*copies));
+            }
+        }
+        dot += "}\n";
+        dot
+    }
+}
+
+pub fn count(copies: Option<u128>) -> String {
+    copies.map_or_else(|| String::from("overflow"), |copies| copies.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card_wins;
+
+    const INPUT: &str = include_str!("../fixtures/example_1.txt");
+
+    #[test]
+    fn trace_table() {
+        let table = card_copies_trace(&card_wins(INPUT), &CopyRule::Below).to_table();
+        assert!(table.contains("Card 5           14  original (1), Card 1 (1), Card 3 (4), Card 4 (8)\n"));
+        assert!(table.ends_with("Total            30\n"));
+    }
+
+    #[test]
+    fn trace_dot() {
+        let dot = card_copies_trace(&card_wins(INPUT), &CopyRule::Below).to_dot();
+        assert!(dot.contains("    card_5 [label=\"Card 5\\n14 copies\"];\n"));
+        assert!(dot.contains("    card_4 -> card_5 [label=\"8\"];\n"));
+        assert_eq!(9, dot.matches(" -> ").count());
+    }
+
+    #[test]
+    fn overflowing_copies() {
+        let generated = crate::generator::generate(0, 200, 10);
+        let trace = card_copies_trace(&card_wins(&generated.input), &CopyRule::Below);
+        assert_eq!(None, trace.total_copies());
+        assert_eq!(Some(1), trace.copies(0));
+        assert!(trace.to_table().ends_with("Total      overflow\n"));
+        assert!(trace.to_dot().contains("Card 200\\noverflow copies"));
+    }
+}
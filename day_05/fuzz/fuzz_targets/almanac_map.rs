@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+const MAP_TYPES: [&str; 7] = ["seed-to-soil", "soil-to-fertilizer", "fertilizer-to-water", "water-to-light",
+    "light-to-temperature", "temperature-to-humidity", "humidity-to-location"];
+
+fuzz_target!(|input: &[u8]| {
+    for map_type in MAP_TYPES {
+        let _ = day_05::AlmanacMap::new(input, map_type.as_bytes());
+    }
+});
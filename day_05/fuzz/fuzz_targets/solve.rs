@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    if let Ok(model) = day_05::parse(input) {
+        let _ = day_05::part_1(&model);
+        let _ = day_05::part_2(&model);
+    }
+});
@@ -0,0 +1,78 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use day_05::{parse, Almanac};
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+// Linear scans get through far fewer lookups in the same time, so they only run over the first of them.
+const LINEAR_LOOKUPS: usize = 1000;
+
+pub fn bench_lookups(mappings: usize, lookups: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(0);
+    let input = almanac_input(&mut rng, mappings);
+    let start = Instant::now();
+    let almanac = parse(input.as_bytes()).expect("Could not read the synthetic almanac");
+    let build_time = start.elapsed();
+    let limit = (mappings as u64) * 1000;
+    let seeds: Vec<u64> = (0..lookups).map(|_| rng.gen_range(0..limit)).collect();
+
+    let linear_seeds = &seeds[..lookups.min(LINEAR_LOOKUPS)];
+    let (linear, linear_time) = time_lookups(|| linear_seeds.iter().map(|seed| linear_location(&almanac, *seed)).collect());
+    let (binary, binary_time) = time_lookups(|| seeds.iter()
+        .map(|seed| almanac.maps().iter().fold(*seed, |value, map| map.map(value)))
+        .collect());
+    let (mut sweep, sweep_time) = time_lookups(|| {
+        let mut values = seeds.clone();
+        for map in almanac.maps() {
+            values.sort_unstable();
+            values = map.map_sorted(&values);
+        }
+        values
+    });
+    assert_eq!(linear, binary[..linear.len()], "binary search disagrees with the linear scan");
+    let mut sorted_binary = binary.clone();
+    sorted_binary.sort_unstable();
+    sweep.sort_unstable();
+    assert_eq!(sorted_binary, sweep, "sorted sweep disagrees with the binary search");
+    format!("{} stages of {} mappings, {} lookups\n\
+             build:         {:>10.2?}\n\
+             linear scan:   {:>10.2?} per lookup\n\
+             binary search: {:>10.2?} per lookup\n\
+             sorted sweep:  {:>10.2?} per lookup\n",
+            CATEGORIES.len() - 1, mappings, lookups, build_time,
+            per_lookup(linear_time, linear.len()), per_lookup(binary_time, lookups), per_lookup(sweep_time, lookups))
+}
+
+// Disjoint ranges 1000 apart, listed in a random order, each sent somewhere random.
+fn almanac_input(rng: &mut StdRng, mappings: usize) -> String {
+    let mut input = String::from("seeds: 0 1\n");
+    for stage in CATEGORIES.windows(2) {
+        let mut srcs: Vec<u64> = (0..mappings as u64).map(|i| i * 1000).collect();
+        srcs.shuffle(rng);
+        input += &format!("\n{}-to-{} map:\n", stage[0], stage[1]);
+        for src in srcs {
+            input += &format!("{} {} {}\n", rng.gen_range(0..(mappings as u64) * 1000), src, rng.gen_range(1..=1000));
+        }
+    }
+    input
+}
+
+fn linear_location(almanac: &Almanac, seed: u64) -> u64 {
+    almanac.maps().iter()
+        .fold(seed, |value, map| map.mappings()
+            .find(|(_, src, len)| value >= *src && value - src < *len)
+            .map_or(value, |(dst, src, _)| dst + (value - src)))
+}
+
+fn time_lookups(lookups: impl FnOnce() -> Vec<u64>) -> (Vec<u64>, Duration) {
+    let start = Instant::now();
+    let locations = black_box(lookups());
+    (locations, start.elapsed())
+}
+
+fn per_lookup(time: Duration, lookups: usize) -> Duration {
+    time / u32::try_from(lookups.max(1)).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,90 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use cli::Generated;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+const VALUE_LIMIT: u64 = 1 << 32;
+
+const MAX_SEED_RANGE: u64 = 1000;
+
+// Seed ranges are kept short so that both answers can be found by mapping every seed one at a time.
+pub fn generate(seed: u64, seed_pairs: usize, stages: usize, mappings: usize) -> Generated {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let categories = categories(stages.max(1));
+    let seed_ranges: Vec<(u64, u64)> = (0..seed_pairs)
+        .map(|_| (rng.gen_range(0..VALUE_LIMIT), rng.gen_range(1..=MAX_SEED_RANGE)))
+        .collect();
+    let mut input = format!("seeds: {}\n", seed_ranges.iter()
+        .map(|(start, len)| format!("{} {}", start, len))
+        .collect::<Vec<String>>()
+        .join(" "));
+    let mut stage_mappings = Vec::new();
+    for stage in categories.windows(2) {
+        let mappings = stage_mappings_for(&mut rng, mappings);
+        input += &format!("\n{}-to-{} map:\n", stage[0], stage[1]);
+        for (dst, src, len) in &mappings {
+            input += &format!("{} {} {}\n", dst, src, len);
+        }
+        stage_mappings.push(mappings);
+    }
+    let location = |seed| stage_mappings.iter()
+        .fold(seed, |value, mappings| map_value(value, mappings));
+    let part_1 = seed_ranges.iter()
+        .flat_map(|(start, len)| [*start, *len])
+        .map(location)
+        .min();
+    let part_2 = seed_ranges.iter()
+        .flat_map(|(start, len)| *start..(start + len))
+        .map(location)
+        .min();
+    Generated { input, part_1, part_2 }
+}
+
+fn categories(stages: usize) -> Vec<String> {
+    if stages == CATEGORIES.len() - 1 {
+        return CATEGORIES.iter().map(|category| String::from(*category)).collect();
+    }
+    let mut categories = vec![String::from("seed")];
+    categories.extend((1..stages).map(|stage| format!("stage{}", stage)));
+    categories.push(String::from("location"));
+    categories
+}
+
+fn stage_mappings_for(rng: &mut StdRng, mappings: usize) -> Vec<(u64, u64, u64)> {
+    let mut bounds: Vec<u64> = (0..(2 * mappings))
+        .map(|_| rng.gen_range(0..VALUE_LIMIT))
+        .collect();
+    bounds.sort();
+    bounds.dedup();
+    let mut stage_mappings: Vec<(u64, u64, u64)> = bounds.chunks_exact(2)
+        .map(|bound| (rng.gen_range(0..VALUE_LIMIT), bound[0], bound[1] - bound[0]))
+        .collect();
+    stage_mappings.shuffle(rng);
+    stage_mappings
+}
+
+fn map_value(value: u64, mappings: &[(u64, u64, u64)]) -> u64 {
+    mappings.iter()
+        .find(|(_, src, len)| value >= *src && value < src + len)
+        .map_or(value, |(dst, src, _)| dst + (value - src))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lowest_location_number, lowest_location_number_actual};
+
+    #[test]
+    fn generated_answers() {
+        for (seed, stages) in (0..20).zip([7, 1, 3, 12].into_iter().cycle()) {
+            let generated = generate(seed, 5, stages, 20);
+            assert_eq!(generated.part_1, Some(lowest_location_number(&generated.input)));
+            assert_eq!(generated.part_2, Some(lowest_location_number_actual(&generated.input)));
+        }
+    }
+}
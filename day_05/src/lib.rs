@@ -0,0 +1,590 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod generator;
+pub mod objectives;
+pub mod pretty;
+pub mod svg;
+pub mod witness;
+#[cfg(test)]
+mod properties;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use alloc::collections::BinaryHeap;
+use core::cmp::{self, Reverse};
+use parsing::{column_error, field, line_error, lines, numbers};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub fn lowest_location_number(input: &str) -> u64 {
+    part_1(&parse(input.as_bytes()).expect("Could not read almanac"))
+}
+
+pub fn lowest_location_number_actual(input: &str) -> u64 {
+    part_2(&parse(input.as_bytes()).expect("Could not read almanac")).expect("no seed ranges")
+}
+
+// Read back from serde through the same checks as the parser, so the maps always run from seed to location.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "AlmanacListing", into = "AlmanacListing"))]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<AlmanacMap>,
+    // Where the seeds are listed in the input, counted from 0. An almanac read back through serde has no input.
+    seeds_line: Option<usize>
+}
+
+impl Almanac {
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    pub fn seed_ranges(&self) -> Vec<(u64, u64)> {
+        seed_ranges(&self.seeds)
+    }
+
+    pub fn maps(&self) -> &[AlmanacMap] {
+        &self.maps
+    }
+
+    pub fn seeds_line(&self) -> Option<usize> {
+        self.seeds_line
+    }
+}
+
+pub fn parse(input: &[u8]) -> Result<Almanac, String> {
+    parse_with(input, &no_includes)
+}
+
+// Reads an included file, given the path of the file that includes it (None for the main input)
+// and the name it is included by. It gives back the included file's own path as well, and the
+// includes inside that file are read against it.
+pub type Resolver<'a> = dyn Fn(Option<&str>, &str) -> Result<(String, Vec<u8>), String> + 'a;
+
+fn no_includes(_: Option<&str>, name: &str) -> Result<(String, Vec<u8>), String> {
+    Err(format!("nothing to read included files with: {}", name))
+}
+
+// Included files are read through the resolver, so the library itself never touches the file system.
+pub fn parse_with(input: &[u8], resolver: &Resolver) -> Result<Almanac, String> {
+    let document = read_document(input, resolver)?;
+    let seeds = document.seeds.ok_or("no seeds line")?;
+    Ok(Almanac { seeds, maps: almanac_maps(document.maps)?, seeds_line: document.seeds_line })
+}
+
+// The maps come out from seed to location, each with its mappings in the order they were listed.
+pub fn to_input(almanac: &Almanac) -> String {
+    pretty::almanac_text(&almanac.seeds, &almanac.maps, false)
+}
+
+pub fn part_1(almanac: &Almanac) -> u64 {
+    #[cfg(feature = "parallel")]
+    let seeds = almanac.seeds.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let seeds = almanac.seeds.iter();
+    seeds.map(|seed| do_mapping(*seed, &almanac.maps))
+        .min()
+        .expect("no seeds")
+}
+
+// Each seed range is mapped on its own, so with the parallel feature the ranges are spread over the thread pool.
+// Seeds only read as ranges in pairs, so there is no answer for an odd count or when every range is empty.
+pub fn part_2(almanac: &Almanac) -> Option<u64> {
+    if !almanac.seeds.len().is_multiple_of(2) {
+        return None
+    }
+    #[cfg(feature = "parallel")]
+    let seed_ranges = seed_ranges(&almanac.seeds).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let seed_ranges = seed_ranges(&almanac.seeds).into_iter();
+    seed_ranges.filter_map(|range| lowest_in_range(range, &almanac.maps))
+        .min()
+}
+
+fn lowest_in_range(range: (u64, u64), maps: &[AlmanacMap]) -> Option<u64> {
+    maps.iter()
+        .fold(vec![range], |ranges, map| map.map_ranges(&ranges))
+        .iter()
+        .map(|(start, _)| *start)
+        .min()
+}
+
+fn do_mapping(seed: u64, maps: &[AlmanacMap]) -> u64 {
+    maps.iter()
+        .fold(seed, |acc, map| map.map(acc))
+}
+
+fn almanac_maps(mut available: Vec<AlmanacMap>) -> Result<Vec<AlmanacMap>, String> {
+    let mut maps = Vec::new();
+    let mut category = String::from("seed");
+    while category != "location" {
+        let i = available.iter()
+            .position(|map| map.map_type.strip_prefix(category.as_str()).is_some_and(|rest| rest.starts_with("-to-")))
+            .ok_or_else(|| format!("no map from category: {}", category))?;
+        let map = available.remove(i);
+        category = String::from(&map.map_type[category.len() + "-to-".len()..]);
+        maps.push(map);
+    }
+    Ok(maps)
+}
+
+// The seeds and maps as they are listed, with included files read in place.
+pub(crate) struct Document {
+    pub(crate) seeds: Option<Vec<u64>>,
+    pub(crate) maps: Vec<AlmanacMap>,
+    seeds_line: Option<usize>
+}
+
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+pub(crate) fn read_document(input: &[u8], resolver: &Resolver) -> Result<Document, String> {
+    let mut document = Document { seeds: None, maps: Vec::new(), seeds_line: None };
+    read_lines(input, resolver, None, 0, &mut document)?;
+    Ok(document)
+}
+
+// Blank lines, extra whitespace and anything after a # are ignored. A map listed again replaces
+// the earlier one, so an almanac can include shared maps and then override some of them.
+fn read_lines(input: &[u8], resolver: &Resolver, path: Option<&str>, depth: usize,
+              document: &mut Document) -> Result<(), String> {
+    let mut map: Option<ListedMap> = None;
+    for (i, raw) in lines(input).enumerate() {
+        let at_line = |err: String| line_error(i, err);
+        let line = raw.split(|byte| *byte == b'#').next().unwrap_or(raw).trim_ascii();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(seeds) = field(line, "seeds") {
+            if depth > 0 {
+                return Err(at_line(String::from("seeds can only be listed in the main almanac")))
+            }
+            if document.seeds.is_some() {
+                return Err(at_line(String::from("seeds are listed twice")))
+            }
+            document.seeds = Some(seed_numbers(seeds).map_err(|err| at_line(column_error(raw, seeds, err)))?);
+            document.seeds_line = Some(i);
+        } else if let Some(map_type) = line.strip_suffix(b"map:") {
+            add_map(document, map.take());
+            map = Some((String::from_utf8_lossy(map_type.trim_ascii()).into_owned(), Vec::new(), Vec::new()));
+        } else if let Some(name) = include_name(line) {
+            add_map(document, map.take());
+            if depth == MAX_INCLUDE_DEPTH {
+                return Err(at_line(String::from("includes are nested too deeply")))
+            }
+            let name = name.map_err(at_line)?;
+            let (included_path, included) = resolver(path, name).map_err(at_line)?;
+            read_lines(&included, resolver, Some(&included_path), depth + 1, document)
+                .map_err(|err| at_line(format!("in {}: {}", name, err)))?;
+        } else {
+            let Some((_, mappings, lines)) = map.as_mut() else {
+                return Err(at_line(format!("mapping outside of a map: {}", String::from_utf8_lossy(line))))
+            };
+            mappings.push(AlmanacMapping::new(line).map_err(|err| at_line(column_error(raw, line, err)))?);
+            lines.push((depth == 0).then_some(i));
+        }
+    }
+    add_map(document, map);
+    Ok(())
+}
+
+// A map's type, its mappings and the input line of each mapping, while it is being read.
+type ListedMap = (String, Vec<AlmanacMapping>, Vec<Option<usize>>);
+
+fn add_map(document: &mut Document, map: Option<ListedMap>) {
+    if let Some((map_type, mappings, lines)) = map {
+        document.maps.retain(|map| map.map_type != map_type);
+        document.maps.push(AlmanacMap::from_mappings(map_type, mappings, lines));
+    }
+}
+
+fn include_name(line: &[u8]) -> Option<Result<&str, String>> {
+    let name = line.strip_prefix(b"include")?;
+    if !name.first().is_some_and(u8::is_ascii_whitespace) {
+        return None
+    }
+    Some(core::str::from_utf8(name.trim_ascii())
+        .map_err(|_| String::from("included file name is not valid UTF-8")))
+}
+
+fn seed_numbers(seeds: &[u8]) -> Result<Vec<u64>, String> {
+    checked_seeds(numbers(seeds)?)
+}
+
+fn checked_seeds(seeds: Vec<u64>) -> Result<Vec<u64>, String> {
+    if seeds.is_empty() {
+        return Err(String::from("no seeds listed"))
+    }
+    if let Some(pair) = seeds.chunks_exact(2).find(|pair| pair[0].checked_add(pair[1]).is_none()) {
+        return Err(format!("seed range too large: {} {}", pair[0], pair[1]))
+    }
+    Ok(seeds)
+}
+
+fn seed_ranges(seeds: &[u64]) -> Vec<(u64, u64)> {
+    seeds.chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .filter(|(_, len)| *len > 0)
+        .collect()
+}
+
+// Only the listed mappings are stored, and the sorted pieces are rebuilt when the map is read back.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "MapListing", into = "MapListing"))]
+pub struct AlmanacMap {
+    map_type: String,
+    mappings: Vec<AlmanacMapping>,
+    // The mappings cut into disjoint pieces, sorted by source, with the earlier listed mapping winning any overlap.
+    sorted: Vec<AlmanacMapping>,
+    // Source starts of the sorted pieces, kept apart so the binary search only touches a flat array.
+    starts: Vec<u64>,
+    // The input line of each listed mapping, or None where it came from an included file or serde.
+    lines: Vec<Option<usize>>
+}
+
+impl AlmanacMap {
+    pub fn new(input: &[u8], map_type: &[u8]) -> Result<Self, String> {
+        let map_type = String::from_utf8_lossy(map_type);
+        read_document(input, &no_includes)?
+            .maps
+            .into_iter()
+            .find(|map| map.map_type == map_type)
+            .ok_or_else(|| format!("map type not found: {}", map_type))
+    }
+
+    fn from_mappings(map_type: String, mappings: Vec<AlmanacMapping>, lines: Vec<Option<usize>>) -> Self {
+        let sorted = disjoint_mappings(&mappings);
+        let starts = sorted.iter().map(|mapping| mapping.src).collect();
+        Self { map_type, mappings, sorted, starts, lines }
+    }
+
+    pub fn map_type(&self) -> &str {
+        &self.map_type
+    }
+
+    // Each mapping as (destination start, source start, length), in the order they were listed.
+    pub fn mappings(&self) -> impl Iterator<Item = (u64, u64, u64)> + '_ {
+        self.mappings.iter()
+            .map(|mapping| (mapping.dst, mapping.src, mapping.len))
+    }
+
+    pub fn mapping_lines(&self) -> &[Option<usize>] {
+        &self.lines
+    }
+
+    // The first listed mapping that covers the input, which is the one map applies.
+    pub fn applied_mapping(&self, input: u64) -> Option<(u64, u64, u64)> {
+        self.mappings.iter()
+            .find(|mapping| mapping.map(input).is_some())
+            .map(|mapping| (mapping.dst, mapping.src, mapping.len))
+    }
+
+    pub fn map(&self, input: u64) -> u64 {
+        let i = self.starts.partition_point(|start| *start <= input);
+        if i == 0 {
+            return input
+        }
+        self.sorted[i - 1].map(input).unwrap_or(input)
+    }
+
+    // The inputs have to be sorted, so the map is swept once alongside them instead of searched for each one.
+    pub fn map_sorted(&self, inputs: &[u64]) -> Vec<u64> {
+        let mut i = 0;
+        inputs.iter()
+            .map(|input| {
+                while i < self.sorted.len() && self.sorted[i].src + self.sorted[i].len <= *input {
+                    i += 1;
+                }
+                self.sorted.get(i).and_then(|mapping| mapping.map(*input)).unwrap_or(*input)
+            })
+            .collect()
+    }
+
+    pub fn map_ranges(&self, ranges: &[(u64, u64)]) -> Vec<(u64, u64)> {
+        self.range_flows(ranges)
+            .into_iter()
+            .map(|(_, dst, len)| (dst, len))
+            .collect()
+    }
+
+    // The pieces each range is split into, as (source start, destination start, length).
+    pub fn range_flows(&self, ranges: &[(u64, u64)]) -> Vec<(u64, u64, u64)> {
+        ranges.iter()
+            .flat_map(|range| self.range_flow(*range))
+            .collect()
+    }
+
+    fn range_flow(&self, (start, len): (u64, u64)) -> Vec<(u64, u64, u64)> {
+        let end = start + len;
+        let first = self.sorted.partition_point(|mapping| mapping.src + mapping.len <= start);
+        let mut flows = Vec::new();
+        let mut at = start;
+        for mapping in self.sorted[first..].iter().take_while(|mapping| mapping.src < end) {
+            if at < mapping.src {
+                flows.push((at, at, mapping.src - at));
+                at = mapping.src;
+            }
+            let piece_end = cmp::min(end, mapping.src + mapping.len);
+            flows.push((at, mapping.dst + (at - mapping.src), piece_end - at));
+            at = piece_end;
+        }
+        if at < end {
+            flows.push((at, at, end - at));
+        }
+        flows
+    }
+}
+
+// Sweeps the range boundaries in order, keeping the mappings that cover the current point in a heap
+// keyed by their position in the list, so the first listed one is on top.
+fn disjoint_mappings(mappings: &[AlmanacMapping]) -> Vec<AlmanacMapping> {
+    let mut by_src: Vec<usize> = (0..mappings.len())
+        .filter(|i| mappings[*i].len > 0)
+        .collect();
+    by_src.sort_unstable_by_key(|i| mappings[*i].src);
+    let mut bounds: Vec<u64> = mappings.iter()
+        .flat_map(|mapping| [mapping.src, mapping.src + mapping.len])
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+    let mut covering = BinaryHeap::new();
+    let mut next = 0;
+    let mut pieces: Vec<AlmanacMapping> = Vec::new();
+    for bound in bounds.windows(2) {
+        let (start, end) = (bound[0], bound[1]);
+        while next < by_src.len() && mappings[by_src[next]].src <= start {
+            covering.push(Reverse(by_src[next]));
+            next += 1;
+        }
+        while covering.peek().is_some_and(|Reverse(i)| mappings[*i].src + mappings[*i].len <= start) {
+            covering.pop();
+        }
+        let Some(Reverse(i)) = covering.peek() else {
+            continue
+        };
+        let dst = mappings[*i].dst + (start - mappings[*i].src);
+        match pieces.last_mut() {
+            Some(last) if last.src + last.len == start && last.dst + last.len == dst => last.len += end - start,
+            _ => pieces.push(AlmanacMapping { src: start, dst, len: end - start })
+        }
+    }
+    pieces
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct AlmanacListing {
+    seeds: Vec<u64>,
+    maps: Vec<AlmanacMap>
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AlmanacListing> for Almanac {
+    type Error = String;
+
+    fn try_from(listing: AlmanacListing) -> Result<Self, String> {
+        Ok(Self { seeds: checked_seeds(listing.seeds)?, maps: almanac_maps(listing.maps)?, seeds_line: None })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Almanac> for AlmanacListing {
+    fn from(almanac: Almanac) -> Self {
+        Self { seeds: almanac.seeds, maps: almanac.maps }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct MapListing {
+    map_type: String,
+    mappings: Vec<AlmanacMapping>
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MapListing> for AlmanacMap {
+    type Error = String;
+
+    fn try_from(listing: MapListing) -> Result<Self, String> {
+        let too_large = |mapping: &&AlmanacMapping| {
+            mapping.src.checked_add(mapping.len).is_none() || mapping.dst.checked_add(mapping.len).is_none()
+        };
+        if let Some(mapping) = listing.mappings.iter().find(too_large) {
+            return Err(format!("mapping range too large: {} {} {}", mapping.dst, mapping.src, mapping.len))
+        }
+        let lines = vec![None; listing.mappings.len()];
+        Ok(Self::from_mappings(listing.map_type, listing.mappings, lines))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<AlmanacMap> for MapListing {
+    fn from(map: AlmanacMap) -> Self {
+        Self { map_type: map.map_type, mappings: map.mappings }
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct AlmanacMapping {
+    src: u64,
+    dst: u64,
+    len: u64
+}
+
+impl AlmanacMapping {
+    fn new(line: &[u8]) -> Result<Self, String> {
+        let parts: Vec<u64> = numbers(line)?;
+        let [dst, src, len] = parts[..] else {
+            return Err(format!("mapping should have three numbers: {}", String::from_utf8_lossy(line)))
+        };
+        if src.checked_add(len).is_none() || dst.checked_add(len).is_none() {
+            return Err(format!("mapping range too large: {}", String::from_utf8_lossy(line)))
+        }
+        Ok(Self { src, dst, len })
+    }
+
+    fn map(&self, input: u64) -> Option<u64> {
+        if input >= self.src && input < self.src + self.len {
+            return Some(self.dst + (input - self.src))
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_1() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(35, lowest_location_number(input));
+    }
+
+    #[test]
+    fn part_2() {
+        let input = include_str!("../fixtures/example_1.txt");
+        assert_eq!(46, lowest_location_number_actual(input));
+    }
+
+    #[test]
+    fn parse_once() {
+        let almanac = parse(b"seeds: 79 14 55 13\n\nseed-to-location map:\n50 98 2\n52 50 48\n").unwrap();
+        assert_eq!(13, super::part_1(&almanac));
+        assert_eq!(Some(57), super::part_2(&almanac));
+        assert!(parse(b"seeds: 79 x\n\nseed-to-location map:\n50 98 2\n").is_err());
+        assert!(parse(b"seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n").is_err());
+    }
+
+    #[test]
+    fn no_seed_ranges() {
+        let maps = "\nseed-to-location map:\n50 98 2\n52 50 48\n";
+        let odd = parse(format!("seeds: 79{}", maps).as_bytes()).unwrap();
+        assert_eq!(81, super::part_1(&odd));
+        assert_eq!(None, super::part_2(&odd));
+        assert_eq!(None, super::part_2(&parse(format!("seeds: 79 0 55 0{}", maps).as_bytes()).unwrap()));
+        assert_eq!(Err(String::from("line 1: column 7: seed range too large: 18446744073709551615 5")),
+                   parse(format!("seeds: 18446744073709551615 5{}", maps).as_bytes()).map(|_| ()));
+    }
+
+    #[test]
+    fn malformed_map() {
+        assert!(AlmanacMap::new(b"seeds: 1\n\nseed-to-soil map:\n1 2 3\n", b"soil-to-fertilizer").is_err());
+        assert!(AlmanacMap::new(b"seed-to-soil map:\n1 2\n", b"seed-to-soil").is_err());
+        assert!(AlmanacMap::new(b"seed-to-soil map:\n1 x 3\n", b"seed-to-soil").is_err());
+        assert!(AlmanacMap::new(b"seed-to-soil map:\n1 2 18446744073709551615\n", b"seed-to-soil").is_err());
+    }
+
+    #[test]
+    fn tolerant_format() {
+        let shared = |_: Option<&str>, name: &str| match name {
+            "shared.txt" => Ok((String::from(name),
+                                b"# shared maps\nseed-to-soil map:\n1 1 1\n\nsoil-to-location map:\n0 15 37\n".to_vec())),
+            _ => Err(format!("no such file: {}", name))
+        };
+        let almanac = parse_with(b"seeds:  79   14 55 13   # two ranges\r\n\r\ninclude shared.txt\r\n\
+                                   \tseed-to-soil   map:\r\n50 98 2\r\n  52 50 48  \r\n", &shared).unwrap();
+        assert_eq!(["seed-to-soil", "soil-to-location"], [almanac.maps[0].map_type(), almanac.maps[1].map_type()]);
+        assert_eq!(2, almanac.maps[0].mappings().count());
+        assert_eq!(13, super::part_1(&almanac));
+        assert_eq!(Some(57), super::part_2(&almanac));
+    }
+
+    #[test]
+    fn back_to_input() {
+        let almanac = parse(b"seeds: 79 14\n\nsoil-to-location map:\n0 15 37 # shared\n\nseed-to-soil map:\n52 50 48\n").unwrap();
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n52 50 48\n\nsoil-to-location map:\n0 15 37\n";
+        assert_eq!(input, to_input(&almanac));
+        assert_eq!(super::part_1(&almanac), super::part_1(&parse(input.as_bytes()).unwrap()));
+    }
+
+    #[test]
+    fn source_lines() {
+        let shared = |_: Option<&str>, name: &str| Ok((String::from(name), b"soil-to-location map:\n0 15 37\n".to_vec()));
+        let almanac = parse_with(b"# seeds\n\nseeds: 79 14\n\nseed-to-soil map:\n52 50 48\n\n50 98 2\ninclude shared.txt\n",
+                                 &shared).unwrap();
+        assert_eq!(Some(2), almanac.seeds_line());
+        assert_eq!(&[Some(5), Some(7)], almanac.maps()[0].mapping_lines());
+        assert_eq!(&[None], almanac.maps()[1].mapping_lines());
+    }
+
+    #[test]
+    fn nested_includes() {
+        // Each file is read against the directory of the file that includes it.
+        let files = |including: Option<&str>, name: &str| {
+            let path = match including.and_then(|path| path.rsplit_once('/')) {
+                Some((dir, _)) => format!("{}/{}", dir, name),
+                None => String::from(name)
+            };
+            match path.as_str() {
+                "maps/soil.txt" => Ok((path, b"seed-to-soil map:\n52 50 48\ninclude location.txt\n".to_vec())),
+                "maps/location.txt" => Ok((path, b"soil-to-location map:\n0 15 37\n".to_vec())),
+                _ => Err(format!("no such file: {}", path))
+            }
+        };
+        let almanac = parse_with(b"seeds: 79 14\ninclude maps/soil.txt\n", &files).unwrap();
+        assert_eq!(2, almanac.maps().len());
+        assert_eq!(Err(String::from("line 2: in maps/soil.txt: line 1: no such file: maps/maps/location.txt")),
+                   parse_with(b"seeds: 79 14\ninclude maps/soil.txt\n", &|including, name| match name {
+                       "maps/soil.txt" => Ok((String::from(name), b"include maps/location.txt\n".to_vec())),
+                       _ => files(including, name)
+                   }).map(|_| ()));
+    }
+
+    #[test]
+    fn line_errors() {
+        let missing = |_: Option<&str>, name: &str| Err(format!("no such file: {}", name));
+        assert_eq!(Err(String::from("line 4: column 5: not a number: x")),
+                   parse(b"seeds: 1 2\n\nseed-to-location map:\n  1 x 3\n").map(|_| ()));
+        assert_eq!(Err(String::from("line 1: column 10: not a number: 2x")), parse(b"seeds: 1 2x\n").map(|_| ()));
+        assert_eq!(Err(String::from("line 2: mapping outside of a map: 1 2 3")),
+                   parse(b"seeds: 1 2\n1 2 3\n").map(|_| ()));
+        assert_eq!(Err(String::from("line 2: no such file: maps.txt")),
+                   parse_with(b"seeds: 1 2\ninclude maps.txt\n", &missing).map(|_| ()));
+        let nested = |_: Option<&str>, name: &str| Ok((String::from(name), b"seed-to-location map:\n1 2\n".to_vec()));
+        assert_eq!(Err(String::from("line 3: in maps.txt: line 2: column 1: mapping should have three numbers: 1 2")),
+                   parse_with(b"seeds: 1 2\n\ninclude maps.txt\n", &nested).map(|_| ()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let almanac = parse(b"seeds: 79 14 55 13\n\nseed-to-location map:\n50 98 2\n52 50 48\n").unwrap();
+        let read_back: Almanac = serde_json::from_str(&serde_json::to_string(&almanac).unwrap()).unwrap();
+        assert_eq!(Some(57), super::part_2(&read_back));
+        let too_large = r#"{"map_type":"seed-to-location","mappings":[{"src":1,"dst":2,"len":18446744073709551615}]}"#;
+        assert!(serde_json::from_str::<AlmanacMap>(too_large).is_err());
+        let maps = r#"[{"map_type":"seed-to-location","mappings":[]}]"#;
+        assert!(serde_json::from_str::<Almanac>(&format!(r#"{{"seeds":[18446744073709551615,1],"maps":{}}}"#, maps)).is_err());
+        assert!(serde_json::from_str::<Almanac>(&format!(r#"{{"seeds":[],"maps":{}}}"#, maps)).is_err());
+        assert!(serde_json::from_str::<Almanac>(r#"{"seeds":[1],"maps":[{"map_type":"seed-to-soil","mappings":[]}]}"#).is_err());
+        assert!(serde_json::from_str::<Almanac>(&format!(r#"{{"seeds":[1],"maps":{}}}"#, maps)).is_ok());
+    }
+}
@@ -1,175 +1,116 @@
-use std::fs;
+mod bench;
 
-fn main() {
-    let input = read_input();
-    println!("Part 1: {}", lowest_location_number(&input));
-    println!("Part 2: {}", lowest_location_number_actual(&input));
-}
-
-fn lowest_location_number(input: &str) -> u64 {
-    let seeds = seed_line(&input);
-    let maps = vec!(
-        AlmanacMap::new(&input, "seed-to-soil"),
-        AlmanacMap::new(&input, "soil-to-fertilizer"),
-        AlmanacMap::new(&input, "fertilizer-to-water"),
-        AlmanacMap::new(&input, "water-to-light"),
-        AlmanacMap::new(&input, "light-to-temperature"),
-        AlmanacMap::new(&input, "temperature-to-humidity"),
-        AlmanacMap::new(&input, "humidity-to-location"));
-    seeds.iter()
-        .map(|seed| do_mapping(*seed, &maps))
-        .min()
-        .expect("no seeds")
-}
+use std::{env, fs, process};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use cli::{exit_on_error, input_source, numeric_arg, print_generated, print_part, print_part_or, read_input};
+use day_05::{generator, parse_with, part_1, part_2, pretty, svg, to_input, witness, Almanac};
+use day_05::objectives::Locations;
 
-fn lowest_location_number_actual(input: &str) -> u64 {
-    0
-}
-
-fn do_mapping(seed: u64, maps: &Vec<AlmanacMap>) -> u64 {
-    maps.iter()
-        .fold(seed, |acc, map| map.map(acc))
-}
-
-fn seed_line(input: &str) -> Vec<u64> {
-    let seed_line = input.lines()
-        .next()
-        .expect("no seeds line");
-    seed_line["seeds: ".len()..].split(" ")
-        .map(|num| num.parse().expect("not a number"))
-        .collect()
-}
-
-struct AlmanacMap {
-    mappings: Vec<AlmanacMapping>
-}
-
-impl AlmanacMap {
-    fn new(input: &str, map_type: &str) -> Self {
-        let map_type_line = format!("{} map:\n", map_type);
-        let from_type = input.split(&map_type_line).last().expect("map type not found");
-        let sections_from: Vec<&str> = from_type.split("\n\n").collect();
-        let lines = sections_from.first().expect("map section didn't end");
-        let mappings = lines.lines()
-            .map(|line| AlmanacMapping::new(line))
-            .collect();
-        Self { mappings }
-    }
-
-    fn map(&self, input: u64) -> u64 {
-        for mapping in &self.mappings {
-            match mapping.map(input) {
-                Some(result) => return result,
-                None => continue
+fn main() {
+    let (args, source) = input_source(env::args().collect(), env!("CARGO_MANIFEST_DIR"));
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("convert") => cli::convert("day_05", &args[2..], || Ok(read_almanac(&source)),
+                                         |model: &Almanac| to_input(model)),
+        Some("generate") => {
+            let generated = generator::generate(numeric_arg(&args, 2, 0), numeric_arg(&args, 3, 10),
+                                                numeric_arg(&args, 4, 7), numeric_arg(&args, 5, 30));
+            print_generated(&generated);
+        },
+        Some("bench") => {
+            print!("{}", bench::bench_lookups(numeric_arg(&args, 2, 200_000), numeric_arg(&args, 3, 1_000_000)));
+        },
+        Some("fmt") => {
+            let normalise = !args.iter().any(|arg| arg == "--raw");
+            let input = exit_on_error(read_input(&source));
+            let formatted = exit_on_error(pretty::pretty_print(&input, normalise, &|including, name| read_include(&source, including, name)));
+            print!("{}", formatted);
+        },
+        Some("witness") => {
+            let almanac = read_almanac(&source);
+            let part_1 = witness::part_1_witness(&almanac);
+            print!("Part 1: {}\n{}", part_1.location, part_1);
+            match witness::part_2_witness(&almanac) {
+                Some(part_2) => print!("Part 2: {}\n{}", part_2.location, part_2),
+                None => println!("Part 2: {}", NO_SEED_RANGES)
             }
+        },
+        Some("objectives") => {
+            let almanac = read_almanac(&source);
+            let locations = match args.get(2).map(|arg| arg.as_str()) {
+                Some("ranges") => Locations::of_seed_ranges(&almanac).unwrap_or_else(|| {
+                    eprintln!("there are no seed ranges, there is an odd number of seeds");
+                    process::exit(1)
+                }),
+                _ => Locations::of_seeds(&almanac)
+            };
+            let width = exit_on_error(histogram_width(&locations, numeric_arg(&args, 4, 1 << 28)));
+            print!("{}", objectives(&locations, numeric_arg(&args, 3, 5), width,
+                                    numeric_arg(&args, 5, 0), numeric_arg(&args, 6, u64::MAX)));
+        },
+        Some("svg") => {
+            let seed_ranges = args.get(2).is_some_and(|arg| arg == "ranges");
+            print!("{}", svg::to_svg(&read_almanac(&source), seed_ranges));
+        },
+        _ => {
+            let json = args.iter().any(|arg| arg == "--json");
+            let input = exit_on_error(read_input(&source));
+            let start = Instant::now();
+            let almanac = exit_on_error(parse_with(&input, &|including, name| read_include(&source, including, name)));
+            let parse_time = start.elapsed();
+            print_part(5, json, 1, &input, parse_time, || part_1(&almanac));
+            print_part_or(5, json, 2, &input, parse_time, NO_SEED_RANGES, || part_2(&almanac));
         }
-        input
     }
 }
 
-struct AlmanacMapping {
-    src: u64,
-    dst: u64,
-    len: u64
-}
+const NO_SEED_RANGES: &str = "none, the seeds don't make any ranges";
 
-impl AlmanacMapping {
-    fn new(line: &str) -> Self {
-        let parts: Vec<u64> = line.split(" ")
-            .map(|part| part.parse().expect("not a number"))
-            .collect();
-        Self { src: parts[1], dst: parts[0], len: parts[2] }
-    }
+// Every bucket a location falls in is printed, so a width that would make more buckets than anyone
+// could read is turned down before the histogram is worked out.
+const MAX_BUCKETS: u64 = 10_000;
 
-    fn map(&self, input: u64) -> Option<u64> {
-        if input >= self.src && input < self.src + self.len {
-            return Some(self.dst + (input - self.src))
-        }
-        None
+fn histogram_width(locations: &Locations, width: u64) -> Result<u64, String> {
+    if width == 0 {
+        return Err(String::from("histogram buckets need a width"))
+    }
+    let buckets = locations.bucket_count(width);
+    if buckets > MAX_BUCKETS {
+        return Err(format!("a width of {} makes {} histogram buckets, but at most {} are shown", width, buckets, MAX_BUCKETS))
     }
+    Ok(width)
 }
 
-fn read_input() -> String {
-    fs::read_to_string("input.txt")
-        .expect("Could not read file")
+fn objectives(locations: &Locations, k: usize, width: u64, low: u64, high: u64) -> String {
+    let (Some(lowest), Some(highest)) = (locations.min(), locations.max()) else {
+        eprintln!("there are no locations, the seeds don't make any ranges");
+        process::exit(1)
+    };
+    let smallest = locations.smallest(k).iter()
+        .map(|location| location.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+    let mut output = format!("Lowest: {}\nHighest: {}\n{} lowest: {}\nWithin {}..{}: {}\nHistogram (width {}):\n",
+                             lowest, highest, k, smallest,
+                             low, high, locations.count_within(low, high), width);
+    for (start, count) in locations.histogram(width) {
+        output += &format!("  {:>12}: {}\n", start, count);
+    }
+    output
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part_1() {
-        let input = "seeds: 79 14 55 13\n\
-        \n\
-        seed-to-soil map:\n\
-        50 98 2\n\
-        52 50 48\n\
-        \n\
-        soil-to-fertilizer map:\n\
-        0 15 37\n\
-        37 52 2\n\
-        39 0 15\n\
-        \n\
-        fertilizer-to-water map:\n\
-        49 53 8\n\
-        0 11 42\n\
-        42 0 7\n\
-        57 7 4\n\
-        \n\
-        water-to-light map:\n\
-        88 18 7\n\
-        18 25 70\n\
-        \n\
-        light-to-temperature map:\n\
-        45 77 23\n\
-        81 45 19\n\
-        68 64 13\n\
-        \n\
-        temperature-to-humidity map:\n\
-        0 69 1\n\
-        1 0 69\n\
-        \n\
-        humidity-to-location map:\n\
-        60 56 37\n\
-        56 93 4";
-        assert_eq!(35, lowest_location_number(input));
-    }
+fn read_almanac(source: &Option<PathBuf>) -> Almanac {
+    exit_on_error(read_input(source)
+        .and_then(|input| parse_with(&input, &|including, name| read_include(source, including, name))))
+}
 
-    fn part_2() {
-        let input = "seeds: 79 14 55 13\n\
-        \n\
-        seed-to-soil map:\n\
-        50 98 2\n\
-        52 50 48\n\
-        \n\
-        soil-to-fertilizer map:\n\
-        0 15 37\n\
-        37 52 2\n\
-        39 0 15\n\
-        \n\
-        fertilizer-to-water map:\n\
-        49 53 8\n\
-        0 11 42\n\
-        42 0 7\n\
-        57 7 4\n\
-        \n\
-        water-to-light map:\n\
-        88 18 7\n\
-        18 25 70\n\
-        \n\
-        light-to-temperature map:\n\
-        45 77 23\n\
-        81 45 19\n\
-        68 64 13\n\
-        \n\
-        temperature-to-humidity map:\n\
-        0 69 1\n\
-        1 0 69\n\
-        \n\
-        humidity-to-location map:\n\
-        60 56 37\n\
-        56 93 4";
-        assert_eq!(46, lowest_location_number_actual(input));
-    }
-}
\ No newline at end of file
+// Included files are found next to the file that includes them. For the main input that is next to
+// the input, or in the current directory when it comes from stdin.
+fn read_include(source: &Option<PathBuf>, including: Option<&str>, name: &str) -> Result<(String, Vec<u8>), String> {
+    let path = including.map(Path::new)
+        .or(source.as_deref())
+        .and_then(Path::parent)
+        .map_or_else(|| PathBuf::from(name), |dir| dir.join(name));
+    let contents = fs::read(&path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+    Ok((path.to_string_lossy().into_owned(), contents))
+}
@@ -0,0 +1,147 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use crate::Almanac;
+
+// Where the seeds end up, as (start, length) ranges. A single seed is a range of one, and a seed range
+// stays a handful of ranges however many seeds it holds, so every aggregate is worked out on the ranges.
+pub struct Locations {
+    ranges: Vec<(u64, u64)>
+}
+
+impl Locations {
+    pub fn of_seeds(almanac: &Almanac) -> Self {
+        let seeds: Vec<(u64, u64)> = almanac.seeds().iter().map(|seed| (*seed, 1)).collect();
+        Self::of_ranges(almanac, seeds)
+    }
+
+    // Like part 2, an odd number of seeds doesn't make seed ranges.
+    pub fn of_seed_ranges(almanac: &Almanac) -> Option<Self> {
+        almanac.seeds().len().is_multiple_of(2).then(|| Self::of_ranges(almanac, almanac.seed_ranges()))
+    }
+
+    fn of_ranges(almanac: &Almanac, seeds: Vec<(u64, u64)>) -> Self {
+        let ranges = almanac.maps().iter()
+            .fold(seeds, |ranges, map| map.map_ranges(&ranges));
+        Self { ranges }
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.spans().map(|(start, _)| start).min()
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        self.spans().map(|(_, last)| last).max()
+    }
+
+    pub fn smallest(&self, k: usize) -> Vec<u64> {
+        let mut smallest = Vec::new();
+        for (start, end) in self.merged() {
+            if smallest.len() == k {
+                break;
+            }
+            let take = end.saturating_sub(start).min((k - smallest.len()) as u64);
+            smallest.extend(start..start + take);
+        }
+        smallest
+    }
+
+    // How many seeds land in low..high, counting a seed once for every time it is listed.
+    pub fn count_within(&self, low: u64, high: u64) -> u64 {
+        self.ranges.iter()
+            .map(|(start, len)| start.saturating_add(*len).min(high).saturating_sub(*start.max(&low)))
+            .sum()
+    }
+
+    // How many buckets of the given width histogram goes through, which can be far more than there
+    // are locations when the width is small.
+    pub fn bucket_count(&self, width: u64) -> u64 {
+        self.spans()
+            .map(|(start, last)| last / width - start / width + 1)
+            .fold(0, u64::saturating_add)
+    }
+
+    // Seed counts for the buckets of the given width that any location falls in, as (bucket start, count)
+    // in order. Empty buckets are left out, so far apart locations don't need every bucket between them.
+    pub fn histogram(&self, width: u64) -> Vec<(u64, u64)> {
+        let mut counts: BTreeMap<u64, u64> = BTreeMap::new();
+        for (start, last) in self.spans() {
+            for bucket in (start / width)..=(last / width) {
+                let bucket_start = bucket * width;
+                let bucket_last = bucket_start.saturating_add(width - 1);
+                *counts.entry(bucket_start).or_insert(0) += last.min(bucket_last) - start.max(bucket_start) + 1;
+            }
+        }
+        counts.into_iter().collect()
+    }
+
+    // The non-empty ranges as (start, last), so the last location is found without overflowing at u64::MAX.
+    fn spans(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.ranges.iter()
+            .filter(|(_, len)| *len > 0)
+            .map(|(start, len)| (*start, start.saturating_add(len - 1)))
+    }
+
+    // The distinct locations as sorted, non-overlapping start..end ranges.
+    fn merged(&self) -> Vec<(u64, u64)> {
+        let mut ranges: Vec<(u64, u64)> = self.ranges.iter().map(|(start, len)| (*start, start.saturating_add(*len))).collect();
+        ranges.sort_unstable();
+        let mut merged: Vec<(u64, u64)> = Vec::new();
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end))
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const INPUT: &[u8] = b"seeds: 79 14 55 13\n\
+        \n\
+        seed-to-soil map:\n\
+        50 98 2\n\
+        52 50 48\n\
+        \n\
+        soil-to-location map:\n\
+        0 15 37\n\
+        80 60 5\n";
+
+    #[test]
+    fn seed_objectives() {
+        let locations = Locations::of_seeds(&parse(INPUT).unwrap());
+        assert_eq!((Some(13), Some(81)), (locations.min(), locations.max()));
+        assert_eq!(vec![13, 14, 57], locations.smallest(3));
+        assert_eq!(2, locations.count_within(50, 82));
+        assert_eq!(vec![(0, 2), (32, 1), (64, 1)], locations.histogram(32));
+    }
+
+    #[test]
+    fn seed_range_objectives() {
+        let locations = Locations::of_seed_ranges(&parse(INPUT).unwrap()).unwrap();
+        assert_eq!((Some(57), Some(94)), (locations.min(), locations.max()));
+        assert_eq!(vec![57, 58, 59, 65, 66], locations.smallest(5));
+        assert_eq!(27, locations.count_within(0, 100));
+        assert_eq!(vec![(50, 3), (60, 5), (80, 14), (90, 5)], locations.histogram(10));
+        assert_eq!(5, locations.bucket_count(10));
+        assert!(Locations::of_seed_ranges(&parse(b"seeds: 79 14 55\n\nseed-to-location map:\n").unwrap()).is_none());
+    }
+
+    #[test]
+    fn far_apart_locations() {
+        let almanac = parse(b"seeds: 0 18446744073709551610 5\n\nseed-to-location map:\n").unwrap();
+        let locations = Locations::of_seeds(&almanac);
+        assert_eq!(Some(18446744073709551610), locations.max());
+        assert_eq!(vec![(0, 1), (5, 1), (18446744073709551610, 1)], locations.histogram(5));
+        let locations = Locations::of_seed_ranges(&parse(b"seeds: 18446744073709551610 5\n\nseed-to-location map:\n").unwrap()).unwrap();
+        assert_eq!(Some(u64::MAX - 1), locations.max());
+        assert_eq!(5, locations.count_within(0, u64::MAX));
+        assert_eq!(vec![(u64::MAX >> 40 << 40, 5)], locations.histogram(1 << 40));
+        let locations = Locations::of_seed_ranges(&parse(b"seeds: 0 18446744073709551615\n\nseed-to-location map:\n").unwrap()).unwrap();
+        assert_eq!(u64::MAX / 3, locations.bucket_count(3));
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+use crate::{read_document, AlmanacMap, Resolver};
+
+// Without normalisation the maps and their mappings come out in the order they were read,
+// so a well-formed almanac without comments or includes is reproduced byte for byte. Either way
+// what is printed is the document the parser sees: comments and extra whitespace are dropped,
+// includes are expanded, and a map listed again only appears once, where it was last listed.
+pub fn pretty_print(input: &[u8], normalise: bool, resolver: &Resolver) -> Result<String, String> {
+    let document = read_document(input, resolver)?;
+    let seeds = document.seeds.ok_or("no seeds line")?;
+    let mut maps = document.maps;
+    if normalise {
+        maps = topological_order(maps);
+    }
+    let mut output = almanac_text(&seeds, &maps, normalise);
+    if !normalise && !input.ends_with(b"\n") {
+        output.pop();
+    }
+    Ok(output)
+}
+
+pub(crate) fn almanac_text(seeds: &[u64], maps: &[AlmanacMap], normalise: bool) -> String {
+    let mut output = format!("seeds: {}\n", seeds.iter().map(|seed| format!("{}", seed)).collect::<Vec<String>>().join(" "));
+    for map in maps {
+        output += &format!("\n{} map:\n", map.map_type());
+        let mappings = if normalise { normalised_mappings(map) } else { map.mappings().collect() };
+        for (dst, src, len) in mappings {
+            output += &format!("{} {} {}\n", dst, src, len);
+        }
+    }
+    output
+}
+
+// Maps follow each other from seed onwards; any that aren't on that path keep their order at the end.
+fn topological_order(mut maps: Vec<AlmanacMap>) -> Vec<AlmanacMap> {
+    let mut ordered = Vec::new();
+    let mut category = String::from("seed");
+    while let Some(i) = maps.iter().position(|map| map.map_type().split_once("-to-").is_some_and(|(from, _)| from == category)) {
+        let map = maps.remove(i);
+        category = String::from(map.map_type().split_once("-to-").map_or("", |(_, to)| to));
+        ordered.push(map);
+    }
+    ordered.extend(maps);
+    ordered
+}
+
+// Splitting everything below the last source end through the map gives the pieces sorted out of any overlaps,
+// with the identity gaps between mappings as pieces of their own. Pieces that continue the same offset are merged.
+fn normalised_mappings(map: &AlmanacMap) -> Vec<(u64, u64, u64)> {
+    let end = map.mappings().map(|(_, src, len)| src + len).max().unwrap_or(0);
+    let mut pieces = map.range_flows(&[(0, end)]);
+    pieces.retain(|(_, _, len)| *len > 0);
+    pieces.sort_unstable();
+    let mut merged: Vec<(u64, u64, u64)> = Vec::new();
+    for (src, dst, len) in pieces {
+        match merged.last_mut() {
+            Some(last) if last.0 + last.2 == src && last.1 + last.2 == dst => last.2 += len,
+            _ => merged.push((src, dst, len))
+        }
+    }
+    merged.into_iter()
+        .map(|(src, dst, len)| (dst, src, len))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_includes(_: Option<&str>, name: &str) -> Result<(String, Vec<u8>), String> {
+        Err(format!("no such file: {}", name))
+    }
+
+    #[test]
+    fn round_trip() {
+        let input = "seeds: 79 14 55 13\n\
+            \n\
+            soil-to-location map:\n\
+            0 15 37\n\
+            \n\
+            seed-to-soil map:\n\
+            52 50 48\n\
+            50 98 2\n";
+        assert_eq!(input, pretty_print(input.as_bytes(), false, &no_includes).unwrap());
+        assert_eq!(input.trim_end(), pretty_print(input.trim_end().as_bytes(), false, &no_includes).unwrap());
+    }
+
+    #[test]
+    fn raw_keeps_only_the_parsed_document() {
+        let shared = |_: Option<&str>, name: &str| Ok((String::from(name), b"soil-to-location map:\n0 15 37\n".to_vec()));
+        let input = b"seeds:  79 14  # two seeds\n\
+            \n\
+            seed-to-soil map:\n\
+            1 2 3\n\
+            include shared.txt\n\
+            \n\
+            \n\
+            seed-to-soil map:\n\
+            52   50 48\n";
+        assert_eq!("seeds: 79 14\n\
+            \n\
+            soil-to-location map:\n\
+            0 15 37\n\
+            \n\
+            seed-to-soil map:\n\
+            52 50 48\n", pretty_print(input, false, &shared).unwrap());
+    }
+
+    #[test]
+    fn normalised() {
+        let input = b"seeds: 79 14\n\
+            \n\
+            soil-to-location map:\n\
+            20 10 5\n\
+            25 15 5\n\
+            3 3 2\n\
+            \n\
+            seed-to-soil map:\n\
+            52 50 48\n\
+            50 98 2\n\
+            0 60 10\n";
+        assert_eq!("seeds: 79 14\n\
+            \n\
+            seed-to-soil map:\n\
+            0 0 50\n\
+            52 50 48\n\
+            50 98 2\n\
+            \n\
+            soil-to-location map:\n\
+            0 0 10\n\
+            20 10 10\n", pretty_print(input, true, &no_includes).unwrap());
+    }
+}
@@ -0,0 +1,86 @@
+use proptest::prelude::*;
+use crate::{lowest_location_number, lowest_location_number_actual, parse};
+use crate::objectives::Locations;
+
+type Mapping = (u64, u64, u64);
+
+fn almanac() -> impl Strategy<Value = (Vec<(u64, u64)>, Vec<Vec<Mapping>>)> {
+    let seed_ranges = prop::collection::vec((0..200_u64, 1..50_u64), 1..4);
+    let mapping = (0..300_u64, 0..300_u64, 1..100_u64);
+    let stages = prop::collection::vec(prop::collection::vec(mapping, 1..5), 1..5);
+    (seed_ranges, stages)
+}
+
+fn almanac_input(seed_ranges: &[(u64, u64)], stages: &[Vec<Mapping>]) -> String {
+    let mut input = format!("seeds: {}\n", seed_ranges.iter()
+        .map(|(start, len)| format!("{} {}", start, len))
+        .collect::<Vec<String>>()
+        .join(" "));
+    for (i, mappings) in stages.iter().enumerate() {
+        let from = if i == 0 { String::from("seed") } else { format!("stage{}", i) };
+        let to = if i == stages.len() - 1 { String::from("location") } else { format!("stage{}", i + 1) };
+        input += &format!("\n{}-to-{} map:\n", from, to);
+        for (dst, src, len) in mappings {
+            input += &format!("{} {} {}\n", dst, src, len);
+        }
+    }
+    input
+}
+
+fn location_per_seed(seed: u64, stages: &[Vec<Mapping>]) -> u64 {
+    stages.iter()
+        .fold(seed, |value, mappings| mappings.iter()
+            .find(|(_, src, len)| value >= *src && value < src + len)
+            .map_or(value, |(dst, src, _)| dst + (value - src)))
+}
+
+proptest! {
+    #[test]
+    fn lowest_location_matches_per_seed_mapping((seed_ranges, stages) in almanac()) {
+        let input = almanac_input(&seed_ranges, &stages);
+        let expected = seed_ranges.iter()
+            .flat_map(|(start, len)| [*start, *len])
+            .map(|seed| location_per_seed(seed, &stages))
+            .min();
+        prop_assert_eq!(expected, Some(lowest_location_number(&input)));
+    }
+
+    #[test]
+    fn interval_mapping_matches_per_seed_mapping((seed_ranges, stages) in almanac()) {
+        let input = almanac_input(&seed_ranges, &stages);
+        let expected = seed_ranges.iter()
+            .flat_map(|(start, len)| *start..(start + len))
+            .map(|seed| location_per_seed(seed, &stages))
+            .min();
+        prop_assert_eq!(expected, Some(lowest_location_number_actual(&input)));
+    }
+
+    #[test]
+    fn sorted_sweep_matches_lookups((seed_ranges, stages) in almanac(),
+                                     mut inputs in prop::collection::vec(0..400_u64, 0..50)) {
+        let almanac = parse(almanac_input(&seed_ranges, &stages).as_bytes()).unwrap();
+        inputs.sort_unstable();
+        for map in almanac.maps() {
+            let expected: Vec<u64> = inputs.iter().map(|input| map.map(*input)).collect();
+            prop_assert_eq!(expected, map.map_sorted(&inputs));
+        }
+    }
+
+    #[test]
+    fn range_objectives_match_per_seed_mapping((seed_ranges, stages) in almanac(),
+                                                 low in 0..400_u64, span in 0..200_u64) {
+        let locations = Locations::of_seed_ranges(&parse(almanac_input(&seed_ranges, &stages).as_bytes()).unwrap()).unwrap();
+        let mut expected: Vec<u64> = seed_ranges.iter()
+            .flat_map(|(start, len)| *start..(start + len))
+            .map(|seed| location_per_seed(seed, &stages))
+            .collect();
+        prop_assert_eq!(expected.iter().max().copied(), locations.max());
+        let within = expected.iter().filter(|location| (low..low + span).contains(*location)).count();
+        prop_assert_eq!(within as u64, locations.count_within(low, low + span));
+        prop_assert_eq!(expected.len() as u64, locations.histogram(7).iter().map(|(_, count)| count).sum::<u64>());
+        expected.sort_unstable();
+        expected.dedup();
+        expected.truncate(5);
+        prop_assert_eq!(expected, locations.smallest(5));
+    }
+}
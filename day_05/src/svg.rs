@@ -0,0 +1,178 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::iter;
+use crate::Almanac;
+
+const MARGIN: f64 = 60.0;
+const STAGE_WIDTH: f64 = 160.0;
+const TOP: f64 = 40.0;
+const PLOT_HEIGHT: f64 = 560.0;
+
+// Every category is a vertical axis and every mapping a band from its source range on one axis
+// to its destination range on the next. The seeds, or the seed ranges, are drawn on top.
+pub fn to_svg(almanac: &Almanac, seed_ranges: bool) -> String {
+    let maps = almanac.maps();
+    let scale = Scale::new(almanac, seed_ranges);
+    let width = MARGIN * 2.0 + STAGE_WIDTH * maps.len() as f64;
+    let height = TOP * 2.0 + PLOT_HEIGHT;
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+                           viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">\n", width, height);
+    svg += "  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n";
+
+    svg += "  <g class=\"categories\">\n";
+    let categories = iter::once("seed")
+        .chain(maps.iter().map(|map| map.map_type().split("-to-").last().unwrap_or("")));
+    for (i, category) in categories.enumerate() {
+        let x = MARGIN + STAGE_WIDTH * i as f64;
+        svg += &format!("    <line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{0:.1}\" y2=\"{2:.1}\" stroke=\"#999\"/>\n",
+                        x, TOP, TOP + PLOT_HEIGHT);
+        svg += &format!("    <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n", x, TOP - 12.0, escape(category));
+    }
+    svg += "  </g>\n";
+
+    svg += "  <g class=\"mappings\">\n";
+    for (stage, map) in maps.iter().enumerate() {
+        for (i, (dst, src, len)) in map.mappings().enumerate() {
+            svg += &format!("    <polygon points=\"{}\" fill=\"hsl({}, 60%, 55%)\" fill-opacity=\"0.35\">\
+                             <title>{}: {}..{} to {}..{}</title></polygon>\n",
+                            scale.band(stage, src, dst, len), (i * 47) % 360, escape(map.map_type()),
+                            src, src.saturating_add(len), dst, dst.saturating_add(len));
+        }
+    }
+    svg += "  </g>\n";
+
+    svg += "  <g class=\"seeds\">\n";
+    if seed_ranges {
+        for (start, seeds) in almanac.seed_ranges() {
+            let mut ranges = vec![(start, seeds)];
+            for (stage, map) in maps.iter().enumerate() {
+                let flows = map.range_flows(&ranges);
+                for (src, dst, len) in &flows {
+                    svg += &format!("    <polygon points=\"{}\" fill=\"#b03a2e\" fill-opacity=\"0.7\">\
+                                     <title>seeds {}..{}</title></polygon>\n",
+                                    scale.band(stage, *src, *dst, *len), start, start.saturating_add(seeds));
+                }
+                ranges = flows.into_iter().map(|(_, dst, len)| (dst, len)).collect();
+            }
+        }
+    } else {
+        for seed in almanac.seeds() {
+            let values: Vec<u64> = iter::once(*seed)
+                .chain(maps.iter().scan(*seed, |value, map| {
+                    *value = map.map(*value);
+                    Some(*value)
+                }))
+                .collect();
+            let points = values.iter().enumerate()
+                .map(|(i, value)| format!("{:.1},{:.1}", MARGIN + STAGE_WIDTH * i as f64, scale.y(*value)))
+                .collect::<Vec<String>>()
+                .join(" ");
+            svg += &format!("    <polyline points=\"{}\" fill=\"none\" stroke=\"#b03a2e\" stroke-width=\"1.5\">\
+                             <title>seed {} to location {}</title></polyline>\n",
+                            points, seed, values[values.len() - 1]);
+        }
+    }
+    svg += "  </g>\n";
+    svg += "</svg>\n";
+    svg
+}
+
+// Category names come straight from the almanac, so they could hold markup of their own.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&apos;",
+            _ => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+struct Scale {
+    max: u64
+}
+
+impl Scale {
+    // Mapped values always land inside a destination range or keep their own value,
+    // so the largest range end or seed bounds every value that is drawn.
+    fn new(almanac: &Almanac, seed_ranges: bool) -> Self {
+        let mapping_ends = almanac.maps().iter()
+            .flat_map(|map| map.mappings())
+            .map(|(dst, src, len)| dst.saturating_add(len).max(src.saturating_add(len)));
+        let seed_ends: Vec<u64> = if seed_ranges {
+            almanac.seed_ranges().iter().map(|(start, len)| start.saturating_add(*len)).collect()
+        } else {
+            almanac.seeds().to_vec()
+        };
+        let max = mapping_ends.chain(seed_ends).max().unwrap_or(0).max(1);
+        Self { max }
+    }
+
+    fn y(&self, value: u64) -> f64 {
+        TOP + value as f64 / self.max as f64 * PLOT_HEIGHT
+    }
+
+    // Bands are at least a pixel thick, so short ranges in a large almanac stay visible.
+    fn band(&self, stage: usize, src: u64, dst: u64, len: u64) -> String {
+        let x_src = MARGIN + STAGE_WIDTH * stage as f64;
+        let x_dst = x_src + STAGE_WIDTH;
+        let (src_top, dst_top) = (self.y(src), self.y(dst));
+        let src_bottom = self.y(src.saturating_add(len)).max(src_top + 1.0);
+        let dst_bottom = self.y(dst.saturating_add(len)).max(dst_top + 1.0);
+        format!("{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}",
+                x_src, src_top, x_dst, dst_top, x_dst, dst_bottom, x_src, src_bottom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const INPUT: &[u8] = b"seeds: 79 14 55 13\n\
+        \n\
+        seed-to-soil map:\n\
+        50 98 2\n\
+        52 50 48\n\
+        \n\
+        soil-to-location map:\n\
+        0 15 37\n";
+
+    #[test]
+    fn seeds_svg() {
+        let svg = to_svg(&parse(INPUT).unwrap(), false);
+        assert!(svg.contains(">soil</text>"));
+        assert!(svg.contains("<title>seed-to-soil: 98..100 to 50..52</title>"));
+        assert_eq!(3, svg.matches("<polygon").count());
+        assert_eq!(4, svg.matches("<polyline").count());
+        assert!(svg.contains("<title>seed 14 to location 14</title>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn seed_ranges_svg() {
+        let svg = to_svg(&parse(INPUT).unwrap(), true);
+        assert_eq!(0, svg.matches("<polyline").count());
+        assert_eq!(2, svg.matches("<title>seeds 79..93</title>").count());
+        assert_eq!(2, svg.matches("<title>seeds 55..68</title>").count());
+    }
+
+    #[test]
+    fn escaped_and_saturated() {
+        let almanac = parse(b"seeds: 18446744073709551614 1\n\n\
+            seed-to-<b>&\"x\" map:\n\
+            0 18446744073709551614 1\n\n\
+            <b>&\"x\"-to-location map:\n").unwrap();
+        let svg = to_svg(&almanac, true);
+        assert!(!svg.contains("<b>"));
+        assert!(svg.contains(">&lt;b&gt;&amp;&quot;x&quot;</text>"));
+        assert!(svg.contains("<title>seed-to-&lt;b&gt;&amp;&quot;x&quot;: 18446744073709551614..18446744073709551615 to 0..1</title>"));
+        assert!(svg.contains("<title>seeds 18446744073709551614..18446744073709551615</title>"));
+    }
+}
@@ -0,0 +1,104 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use crate::{Almanac, AlmanacMap};
+
+// The seed behind a lowest location, and the value and mapping line it went through at every stage.
+pub struct Witness {
+    pub seed: u64,
+    pub location: u64,
+    pub stages: Vec<Stage>
+}
+
+pub struct Stage {
+    pub map_type: String,
+    pub value: u64,
+    // The (destination, source, length) line that applied, or None where the value passed through unchanged.
+    pub mapping: Option<(u64, u64, u64)>
+}
+
+pub fn part_1_witness(almanac: &Almanac) -> Witness {
+    let seed = almanac.seeds().iter()
+        .copied()
+        .min_by_key(|seed| almanac.maps().iter().fold(*seed, |value, map| map.map(value)))
+        .expect("no seeds");
+    witness(seed, almanac.maps())
+}
+
+// Every piece a seed range is cut into keeps a fixed offset from the seeds it came from,
+// so carrying the first seed of each piece along gives the seed behind the lowest location.
+pub fn part_2_witness(almanac: &Almanac) -> Option<Witness> {
+    if !almanac.seeds().len().is_multiple_of(2) {
+        return None
+    }
+    let mut pieces: Vec<(u64, u64, u64)> = almanac.seed_ranges().iter()
+        .map(|(start, len)| (*start, *len, *start))
+        .collect();
+    for map in almanac.maps() {
+        pieces = pieces.iter()
+            .flat_map(|(start, len, seed)| map.range_flows(&[(*start, *len)]).into_iter()
+                .map(move |(src, dst, len)| (dst, len, seed + (src - start))))
+            .collect();
+    }
+    let (_, _, seed) = pieces.iter()
+        .min_by_key(|(start, _, _)| *start)?;
+    Some(witness(*seed, almanac.maps()))
+}
+
+fn witness(seed: u64, maps: &[AlmanacMap]) -> Witness {
+    let mut value = seed;
+    let stages: Vec<Stage> = maps.iter()
+        .map(|map| {
+            let mapping = map.applied_mapping(value);
+            value = map.map(value);
+            Stage { map_type: String::from(map.map_type()), value, mapping }
+        })
+        .collect();
+    Witness { seed, location: value, stages }
+}
+
+impl fmt::Display for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "seed {}", self.seed)?;
+        for stage in &self.stages {
+            let category = stage.map_type.split("-to-").last().unwrap_or("");
+            match stage.mapping {
+                Some((dst, src, len)) => writeln!(f, "{} {} ({}: {} {} {})", category, stage.value, stage.map_type,
+                                                  dst, src, len)?,
+                None => writeln!(f, "{} {} ({}: identity)", category, stage.value, stage.map_type)?
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use super::*;
+    use crate::parse;
+
+    const INPUT: &[u8] = include_bytes!("../fixtures/example_1.txt");
+
+    #[test]
+    fn seed_witness() {
+        let witness = part_1_witness(&parse(INPUT).unwrap());
+        assert_eq!((13, 35), (witness.seed, witness.location));
+        assert_eq!("seed 13\n\
+                    soil 13 (seed-to-soil: identity)\n\
+                    fertilizer 52 (soil-to-fertilizer: 39 0 15)\n\
+                    water 41 (fertilizer-to-water: 0 11 42)\n\
+                    light 34 (water-to-light: 18 25 70)\n\
+                    temperature 34 (light-to-temperature: identity)\n\
+                    humidity 35 (temperature-to-humidity: 1 0 69)\n\
+                    location 35 (humidity-to-location: identity)\n", witness.to_string());
+    }
+
+    #[test]
+    fn seed_range_witness() {
+        let witness = part_2_witness(&parse(INPUT).unwrap()).unwrap();
+        assert_eq!((82, 46), (witness.seed, witness.location));
+        assert_eq!(Some((1, 0, 69)), witness.stages[5].mapping);
+        assert_eq!(None, witness.stages[6].mapping);
+    }
+}
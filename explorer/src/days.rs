@@ -0,0 +1,310 @@
+use day_04::rules::ScoringRules;
+use day_04::trace::{card_copies_trace, count};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::view::{Item, Overlay, OverlayKind, Region, View};
+
+// The path is where the input was read from, so day 5 can find the files it includes.
+pub fn view(day: u32, input: &[u8], path: &Path) -> Result<View, String> {
+    match day {
+        1 => day_01(input),
+        2 => day_02(input),
+        3 => day_03(input),
+        4 => day_04(input),
+        5 => day_05(input, path),
+        _ => Err(format!("no explorer view for day {}", day))
+    }
+}
+
+fn day_01(input: &[u8]) -> Result<View, String> {
+    let calibrations = day_01::parse(input)?;
+    let mut view = View::new("Day 1: calibration values", input);
+    view.answers = (day_01::part_1(&calibrations).map_or(String::from("none, a line has no digits"), |part_1| part_1.to_string()),
+                    day_01::part_2(&calibrations).to_string());
+    let lines = view.content_lines();
+    let items = calibrations.iter().zip(lines)
+        .map(|(calibration, line)| Item {
+            label: format!("Line {}: {}", line + 1, calibration.value_actual()),
+            region: Region::line(&view.raw, line),
+            details: vec![
+                match calibration.value() {
+                    Some(value) => format!("Part 1: the first and last digits make {}, which is added", value),
+                    None => String::from("Part 1: this line has no digits, so there is no part 1 answer")
+                },
+                format!("Part 2: the first and last digits or words make {}, which is added",
+                        calibration.value_actual())
+            ]
+        })
+        .collect();
+    view.items = items;
+    Ok(view)
+}
+
+fn day_02(input: &[u8]) -> Result<View, String> {
+    let games = day_02::parse(input)?;
+    let mut view = View::new("Day 2: cube games", input);
+    view.answers = (day_02::part_1(&games).to_string(), day_02::part_2(&games).to_string());
+    let lines = view.content_lines();
+    let items = games.iter().zip(lines)
+        .map(|(game, line)| {
+            let (red, green, blue) = game.mins();
+            let mut details = vec![String::from("Draws:")];
+            if let Some((_, draws)) = view.raw[line].split_once(':') {
+                details.extend(draws.split(';').map(|draw| format!("  {}", draw.trim())));
+            }
+            details.push(format!("Fewest cubes: {} red, {} green, {} blue", red, green, blue));
+            details.push(if game.within_maxes(12, 13, 14) {
+                format!("Part 1: possible with 12 red, 13 green and 14 blue cubes, so {} is added", game.id())
+            } else {
+                String::from("Part 1: not possible with 12 red, 13 green and 14 blue cubes")
+            });
+            details.push(format!("Part 2: power {} x {} x {} = {}, which is added", red, green, blue, game.power()));
+            Item { label: format!("Game {}", game.id()), region: Region::line(&view.raw, line), details }
+        })
+        .collect();
+    view.items = items;
+    Ok(view)
+}
+
+fn day_03(input: &[u8]) -> Result<View, String> {
+    let schematic = day_03::parse(input)?;
+    let mut view = View::new("Day 3: engine schematic", input);
+    view.answers = (day_03::part_1(&schematic).to_string(),
+                    day_03::part_2(&schematic).map_or(String::from("none, the gear ratios overflow"), |part_2| part_2.to_string()));
+    for (row, numbers_row) in schematic.numbers().iter().enumerate() {
+        for (number, start_pos) in numbers_row.iter().copied() {
+            let region = Region { line: row, start: start_pos, end: start_pos + number.to_string().len() };
+            let is_part_number = schematic.is_part_number(row, number, start_pos);
+            view.overlays.push(Overlay {
+                region,
+                kind: if is_part_number { OverlayKind::Counted } else { OverlayKind::NotCounted }
+            });
+            let mut details = vec![if is_part_number {
+                format!("Part 1: next to a symbol, so {} is added", number)
+            } else {
+                String::from("Part 1: not next to any symbol")
+            }];
+            let gears = schematic.adjacent_gears(row, number, start_pos);
+            if gears.is_empty() {
+                details.push(String::from("Part 2: not next to a gear"));
+            }
+            for (gear_row, gear_col) in gears {
+                let gear_numbers = schematic.gear_numbers(gear_row, gear_col);
+                details.push(if gear_numbers.len() > 1 {
+                    format!("Part 2: the gear at row {}, column {} joins {:?} for a ratio of {}",
+                            gear_row + 1, gear_col + 1, gear_numbers,
+                            gear_numbers.iter().map(|number| u64::from(*number)).product::<u64>())
+                } else {
+                    format!("Part 2: the gear at row {}, column {} has no other number", gear_row + 1, gear_col + 1)
+                });
+            }
+            view.items.push(Item {
+                label: format!("{} at row {}, column {}", number, row + 1, start_pos + 1),
+                region,
+                details
+            });
+        }
+    }
+    let gears: Vec<Overlay> = view.raw.iter().enumerate()
+        .flat_map(|(line, text)| text.chars().enumerate()
+            .filter(|(_, c)| *c == '*')
+            .map(move |(col, _)| Overlay { region: Region { line, start: col, end: col + 1 }, kind: OverlayKind::Gear }))
+        .collect();
+    view.overlays.extend(gears);
+    Ok(view)
+}
+
+fn day_04(input: &[u8]) -> Result<View, String> {
+    let card_wins = day_04::parse(input)?;
+    let rules = ScoringRules::default();
+    let trace = card_copies_trace(&card_wins, &rules.copies);
+    let mut view = View::new("Day 4: scratchcard cascade", input);
+    view.answers = (day_04::part_1(&card_wins).to_string(), count(day_04::part_2(&card_wins)));
+    let lines = view.content_lines();
+    let items = card_wins.iter().enumerate().zip(lines)
+        .map(|((card, wins), line)| {
+            let copies = count(trace.copies(card));
+            let mut details = vec![
+                format!("Part 1: {} matching numbers score {} points", wins, rules.points.points(*wins)),
+                format!("Part 2: {} copies, which are added to the total", copies),
+                String::from("  original (1)")
+            ];
+            details.extend(trace.received(card).iter()
+                .map(|(from, received)| format!("  from Card {} ({})", from + 1, count(*received))));
+            let won = rules.copies.won_cards(card, *wins, card_wins.len());
+            if let (Some(first), Some(last)) = (won.first(), won.last()) {
+                details.push(format!("Each of its copies wins a copy of Cards {} to {}", first + 1, last + 1));
+            }
+            Item {
+                label: format!("Card {}: {} wins, {} copies", card + 1, wins, copies),
+                region: Region::line(&view.raw, line),
+                details
+            }
+        })
+        .collect();
+    view.items = items;
+    Ok(view)
+}
+
+fn day_05(input: &[u8], path: &Path) -> Result<View, String> {
+    let almanac = day_05::parse_with(input, &|including, name| read_include(path, including, name))?;
+    let mut view = View::new("Day 5: almanac maps", input);
+    let part_1 = day_05::part_1(&almanac);
+    let part_2 = day_05::part_2(&almanac);
+    view.answers = (part_1.to_string(),
+                    part_2.map_or(String::from("none, the seeds don't make any ranges"), |part_2| part_2.to_string()));
+    let mut stage_values: Vec<Vec<u64>> = vec![almanac.seeds().to_vec()];
+    for map in almanac.maps() {
+        let values = stage_values[stage_values.len() - 1].iter()
+            .map(|value| map.map(*value))
+            .collect();
+        stage_values.push(values);
+    }
+    let seeds_line = almanac.seeds_line().unwrap_or(0);
+    for (i, seed) in almanac.seeds().iter().enumerate() {
+        let location = stage_values[stage_values.len() - 1][i];
+        let mut details: Vec<String> = almanac.maps().iter().zip(&stage_values[1..])
+            .map(|(map, values)| format!("  {} {}", destination(map.map_type()), values[i]))
+            .collect();
+        details.insert(0, format!("  seed {}", seed));
+        details.push(if location == part_1 {
+            format!("Part 1: location {} is the lowest", location)
+        } else {
+            format!("Part 1: location {} is higher than the lowest, {}", location, part_1)
+        });
+        view.items.push(Item {
+            label: format!("Seed {}: location {}", seed, location),
+            region: Region::line(&view.raw, seeds_line),
+            details
+        });
+    }
+    for (start, len) in almanac.seed_ranges() {
+        let mut ranges = vec![(start, len)];
+        let mut details = Vec::new();
+        for map in almanac.maps() {
+            ranges = map.map_ranges(&ranges);
+            details.push(format!("  {} ranges after {}", ranges.len(), map.map_type()));
+        }
+        let lowest = ranges.iter().map(|(start, _)| *start).min().unwrap_or(0);
+        details.push(if Some(lowest) == part_2 {
+            format!("Part 2: lowest location {} is the lowest of all the ranges", lowest)
+        } else {
+            format!("Part 2: lowest location {} is higher than the lowest, {}", lowest, part_2.unwrap_or(lowest))
+        });
+        view.items.push(Item {
+            label: format!("Seeds {} to {}: location {}", start, start + (len - 1), lowest),
+            region: Region::line(&view.raw, seeds_line),
+            details
+        });
+    }
+    for (stage, map) in almanac.maps().iter().enumerate() {
+        for ((dst, src, len), line) in map.mappings().zip(map.mapping_lines()) {
+            let seeds: Vec<u64> = almanac.seeds().iter().zip(&stage_values[stage])
+                .filter(|(_, value)| **value >= src && **value - src < len)
+                .map(|(seed, _)| *seed)
+                .collect();
+            let shift = i128::from(dst) - i128::from(src);
+            let last = |start: u64| start.saturating_add(len.saturating_sub(1));
+            view.items.push(Item {
+                label: format!("{}: {} to {} ({:+})", map.map_type(), src, last(src), shift),
+                region: Region::line(&view.raw, line.unwrap_or(seeds_line)),
+                details: vec![
+                    if len == 0 {
+                        format!("Maps no {} at all, its length is 0", source(map.map_type()))
+                    } else {
+                        format!("Maps {} {} to {} onto {} {} to {}", source(map.map_type()), src, last(src),
+                                destination(map.map_type()), dst, last(dst))
+                    },
+                    if seeds.is_empty() {
+                        String::from("Part 1: no seed passes through this range")
+                    } else {
+                        format!("Part 1: carries seeds {:?}", seeds)
+                    }
+                ]
+            });
+        }
+    }
+    Ok(view)
+}
+
+// Includes are found next to the file that includes them, as the day 5 command line finds them.
+fn read_include(path: &Path, including: Option<&str>, name: &str) -> Result<(String, Vec<u8>), String> {
+    let path = including.map_or(path, Path::new)
+        .parent()
+        .map_or_else(|| PathBuf::from(name), |dir| dir.join(name));
+    let contents = fs::read(&path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+    Ok((path.to_string_lossy().into_owned(), contents))
+}
+
+fn source(map_type: &str) -> &str {
+    map_type.split("-to-").next().unwrap_or(map_type)
+}
+
+fn destination(map_type: &str) -> &str {
+    map_type.split("-to-").last().unwrap_or(map_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gear_contributions() {
+        let view = view(3, b"467..114..\n...*......\n..35..633.", Path::new("input.txt")).unwrap();
+        assert_eq!(("502".to_string(), "16345".to_string()), view.answers);
+        assert_eq!("467 at row 1, column 1", view.items[0].label);
+        assert_eq!("Part 2: the gear at row 2, column 4 joins [467, 35] for a ratio of 16345", view.items[0].details[1]);
+        assert_eq!(OverlayKind::NotCounted, view.overlays[1].kind);
+        assert_eq!(Overlay { region: Region { line: 1, start: 3, end: 4 }, kind: OverlayKind::Gear },
+                   view.overlays[view.overlays.len() - 1]);
+    }
+
+    #[test]
+    fn cascade_contributions() {
+        let view = view(4, b"Card 1: 41 48 | 41 48\nCard 2: 13 32 | 61 30\nCard 3: 1 | 1", Path::new("input.txt")).unwrap();
+        assert_eq!("Card 2: 0 wins, 2 copies", view.items[1].label);
+        assert_eq!(vec!["Part 1: 0 matching numbers score 0 points", "Part 2: 2 copies, which are added to the total",
+                        "  original (1)", "  from Card 1 (1)"], view.items[1].details[..4]);
+    }
+
+    #[test]
+    fn blank_lines_skipped() {
+        let view = view(4, b"Card 1: 41 48 | 41 48\n\nCard 2: 13 32 | 61 30\n\n", Path::new("input.txt")).unwrap();
+        assert_eq!(2, view.items.len());
+        assert_eq!(2, view.items[1].region.line);
+    }
+
+    #[test]
+    fn seed_paths() {
+        let view = view(5, b"seeds: 79 14 55 13\n\nseed-to-location map:\n50 98 2\n52 50 48\n", Path::new("input.txt")).unwrap();
+        assert_eq!("Seed 79: location 81", view.items[0].label);
+        assert_eq!("Seeds 79 to 92: location 81", view.items[4].label);
+        assert_eq!("seed-to-location: 50 to 97 (+2)", view.items[7].label);
+        assert_eq!(Region { line: 4, start: 0, end: 8 }, view.items[7].region);
+        assert_eq!("Part 1: carries seeds [79, 55]", view.items[7].details[1]);
+    }
+
+    #[test]
+    fn loose_almanac() {
+        let view = view(5, b"# almanac\n  seeds:  79 14\n\nseed-to-location   map:  # padded\n\n0 5 0\n50 98 2\n", Path::new("input.txt")).unwrap();
+        assert_eq!(1, view.items[0].region.line);
+        assert_eq!(1, view.items[2].region.line);
+        assert_eq!("seed-to-location: 5 to 5 (-5)", view.items[3].label);
+        assert_eq!(5, view.items[3].region.line);
+        assert_eq!("Maps no seed at all, its length is 0", view.items[3].details[0]);
+        assert_eq!(6, view.items[4].region.line);
+    }
+
+    #[test]
+    fn included_maps() {
+        let dir = std::env::temp_dir().join(format!("explorer-includes-{}", std::process::id()));
+        fs::create_dir_all(dir.join("maps")).unwrap();
+        fs::write(dir.join("maps/soil.txt"), "include location.txt\n").unwrap();
+        fs::write(dir.join("maps/location.txt"), "seed-to-location map:\n50 98 2\n52 50 48\n").unwrap();
+        let view = view(5, b"seeds: 79 14 55 13\ninclude maps/soil.txt\n", &dir.join("input.txt"));
+        let missing = super::view(5, b"seeds: 79 14\ninclude location.txt\n", &dir.join("input.txt")).err();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(("13".to_string(), "57".to_string()), view.unwrap().answers);
+        assert!(missing.unwrap().starts_with(&format!("line 2: Could not read {}", dir.join("location.txt").display())));
+    }
+}
@@ -0,0 +1,42 @@
+mod days;
+mod ui;
+mod view;
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+use ui::App;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let Some(day) = args.get(1).and_then(|arg| day_number(arg)) else {
+        eprintln!("usage: explorer <day> [input]");
+        process::exit(1)
+    };
+    let path = args.get(2).map_or_else(|| default_input(day), PathBuf::from);
+    let input = fs::read(&path).unwrap_or_else(|err| {
+        eprintln!("Could not read {}: {}", path.display(), err);
+        process::exit(1)
+    });
+    let view = days::view(day, &input, &path).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1)
+    });
+    let mut terminal = ratatui::init();
+    let result = App::new(view).run(&mut terminal);
+    ratatui::restore();
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+// Days can be given as "3" or "day_03".
+fn day_number(arg: &str) -> Option<u32> {
+    arg.strip_prefix("day_").unwrap_or(arg).parse().ok()
+}
+
+fn default_input(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("explorer is not in the repository")
+        .join(format!("day_{:02}", day))
+        .join("input.txt")
+}
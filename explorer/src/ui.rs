@@ -0,0 +1,151 @@
+use std::io;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use crate::view::{Item, Overlay, OverlayKind, Region, View};
+
+pub struct App {
+    view: View,
+    // Overlays grouped by line, so drawing a line only looks at its own.
+    line_overlays: Vec<Vec<Overlay>>,
+    list: ListState
+}
+
+impl App {
+    pub fn new(view: View) -> Self {
+        let mut line_overlays = vec![Vec::new(); view.raw.len()];
+        for overlay in &view.overlays {
+            if let Some(overlays) = line_overlays.get_mut(overlay.region.line) {
+                overlays.push(*overlay);
+            }
+        }
+        let mut list = ListState::default();
+        if !view.items.is_empty() {
+            list.select(Some(0));
+        }
+        Self { view, line_overlays, list }
+    }
+
+    pub fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Down | KeyCode::Char('j') => self.select_by(1),
+                    KeyCode::Up | KeyCode::Char('k') => self.select_by(-1),
+                    KeyCode::PageDown => self.select_by(10),
+                    KeyCode::PageUp => self.select_by(-10),
+                    KeyCode::Home => self.select_by(isize::MIN),
+                    KeyCode::End => self.select_by(isize::MAX),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn select_by(&mut self, offset: isize) {
+        if self.view.items.is_empty() {
+            return;
+        }
+        let selected = self.list.selected().unwrap_or(0)
+            .saturating_add_signed(offset)
+            .min(self.view.items.len() - 1);
+        self.list.select(Some(selected));
+    }
+
+    fn selected(&self) -> Option<&Item> {
+        self.list.selected().and_then(|selected| self.view.items.get(selected))
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [header_area, body_area, footer_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [raw_area, items_area, details_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(20), Constraint::Percentage(30)])
+                .areas(body_area);
+
+        let header = format!("{}   Part 1: {}   Part 2: {}", self.view.title, self.view.answers.0, self.view.answers.1);
+        frame.render_widget(Line::styled(header, Style::new().add_modifier(Modifier::BOLD)), header_area);
+
+        let selected = self.selected().map(|item| item.region);
+        let lines: Vec<Line> = self.view.raw.iter().enumerate()
+            .map(|(line, text)| self.raw_line(line, text, selected))
+            .collect();
+        // Keep the selected line in the middle of the input pane.
+        let scroll = selected.map_or(0, |region| region.line.saturating_sub(usize::from(raw_area.height / 2)));
+        frame.render_widget(Paragraph::new(lines)
+                                .block(Block::bordered().title("Input"))
+                                .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0)),
+                            raw_area);
+
+        let items = List::new(self.view.items.iter().map(|item| item.label.as_str()))
+            .block(Block::bordered().title("Items"))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(items, items_area, &mut self.list);
+
+        let details: Vec<Line> = self.selected()
+            .map_or(Vec::new(), |item| item.details.iter().map(|detail| Line::from(detail.as_str())).collect());
+        frame.render_widget(Paragraph::new(details)
+                                .block(Block::bordered().title("Contribution"))
+                                .wrap(Wrap { trim: false }),
+                            details_area);
+
+        frame.render_widget(Line::from("up/down or j/k: select   PgUp/PgDn: jump   Home/End: first/last   q: quit"),
+                            footer_area);
+    }
+
+    fn raw_line<'a>(&self, line: usize, text: &'a str, selected: Option<Region>) -> Line<'a> {
+        let overlays = &self.line_overlays[line];
+        let spans: Vec<Span> = text.char_indices().enumerate()
+            .map(|(col, (i, c))| {
+                let mut style = Style::new();
+                for overlay in overlays.iter().filter(|overlay| overlay.region.contains(line, col)) {
+                    style = style.fg(color(overlay.kind));
+                }
+                if selected.is_some_and(|region| region.contains(line, col)) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Span::styled(&text[i..i + c.len_utf8()], style)
+            })
+            .collect();
+        Line::from(spans)
+    }
+}
+
+fn color(kind: OverlayKind) -> Color {
+    match kind {
+        OverlayKind::Counted => Color::Green,
+        OverlayKind::NotCounted => Color::Red,
+        OverlayKind::Gear => Color::Yellow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use crate::days;
+    use super::*;
+
+    #[test]
+    fn draws_selected_item() {
+        let mut app = App::new(days::view(2, b"Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 2: 20 red", Path::new("input.txt")).unwrap());
+        app.select_by(1);
+        let mut terminal = Terminal::new(TestBackend::new(120, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Day 2: cube games   Part 1: 1   Part 2: 24"));
+        assert!(text.contains("Part 1: not possible"));
+        assert!(buffer[(1, 3)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(1, 2)].modifier.contains(Modifier::REVERSED));
+    }
+}
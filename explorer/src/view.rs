@@ -0,0 +1,64 @@
+// What the explorer shows for one day: the raw input, the parsed items and how each one adds up.
+pub struct View {
+    pub title: String,
+    pub answers: (String, String),
+    pub raw: Vec<String>,
+    pub overlays: Vec<Overlay>,
+    pub items: Vec<Item>
+}
+
+pub struct Item {
+    pub label: String,
+    pub region: Region,
+    pub details: Vec<String>
+}
+
+// A run of columns on one line of the raw input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Region {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize
+}
+
+impl Region {
+    pub fn line(raw: &[String], line: usize) -> Self {
+        let end = raw.get(line).map_or(0, |text| text.chars().count());
+        Self { line, start: 0, end }
+    }
+
+    pub fn contains(&self, line: usize, col: usize) -> bool {
+        self.line == line && self.start <= col && col < self.end
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverlayKind {
+    Counted,
+    NotCounted,
+    Gear
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Overlay {
+    pub region: Region,
+    pub kind: OverlayKind
+}
+
+impl View {
+    pub fn new(title: &str, input: &[u8]) -> Self {
+        let raw = String::from_utf8_lossy(input).lines()
+            .map(String::from)
+            .collect();
+        Self { title: String::from(title), answers: (String::new(), String::new()), raw, overlays: Vec::new(),
+               items: Vec::new() }
+    }
+
+    // The lines that aren't blank, which are the ones parsed into items.
+    pub fn content_lines(&self) -> Vec<usize> {
+        self.raw.iter().enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
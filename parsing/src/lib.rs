@@ -0,0 +1,209 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Blank lines, such as a trailing one, are skipped, but errors still give the line's place in the input.
+pub fn parse_lines<T: Send>(input: &[u8], parser: impl Fn(&[u8]) -> Result<T, String> + Send + Sync)
+    -> Result<Vec<T>, String> {
+    parse_each(input, |line| !line.trim_ascii().is_empty(), parser)
+}
+
+// Every line is kept, for grids where a blank line is still a row.
+pub fn parse_rows<T: Send>(input: &[u8], parser: impl Fn(&[u8]) -> Result<T, String> + Send + Sync)
+    -> Result<Vec<T>, String> {
+    parse_each(input, |_| true, parser)
+}
+
+// With the parallel feature lines are parsed on the thread pool, but the results are still
+// gathered in line order so the same error is reported either way.
+#[cfg(feature = "parallel")]
+fn parse_each<T: Send>(input: &[u8], keep: impl Fn(&[u8]) -> bool + Send + Sync,
+                       parser: impl Fn(&[u8]) -> Result<T, String> + Send + Sync) -> Result<Vec<T>, String> {
+    input.par_split_inclusive(|byte| *byte == b'\n')
+        .map(trim_line_ending)
+        .map(|line| keep(line).then(|| parser(line)))
+        .collect::<Vec<Option<Result<T, String>>>>()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, result)| result.map(|result| result.map_err(|err| line_error(i, err))))
+        .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn parse_each<T>(input: &[u8], keep: impl Fn(&[u8]) -> bool, parser: impl Fn(&[u8]) -> Result<T, String>)
+    -> Result<Vec<T>, String> {
+    lines(input).enumerate()
+        .filter(|(_, line)| keep(line))
+        .map(|(i, line)| parser(line).map_err(|err| line_error(i, err)))
+        .collect()
+}
+
+pub fn lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    input.split_inclusive(|byte| *byte == b'\n')
+        .map(trim_line_ending)
+}
+
+pub fn trim_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+// Lines are counted from 1, as editors count them.
+pub fn line_error(index: usize, err: String) -> String {
+    format!("line {}: {}", index + 1, err)
+}
+
+// Columns are counted from 1 too, in bytes from the start of the text, and part has to be a slice
+// of the text. An error that already has a column is from a slice further in, so the two add up.
+pub fn column_error(text: &[u8], part: &[u8], err: String) -> String {
+    let Some(offset) = (part.as_ptr() as usize).checked_sub(text.as_ptr() as usize)
+        .filter(|offset| *offset <= text.len()) else {
+        return err
+    };
+    let located = err.strip_prefix("column ")
+        .and_then(|rest| rest.split_once(": "))
+        .and_then(|(column, rest)| Some((column.parse::<usize>().ok()?, rest)));
+    match located {
+        Some((column, rest)) => format!("column {}: {}", offset + column, rest),
+        None => format!("column {}: {}", offset + 1, err)
+    }
+}
+
+// Runs of lines between blank lines, each with the index of its first line.
+pub fn sections(input: &[u8]) -> Vec<(usize, Vec<&[u8]>)> {
+    let mut sections: Vec<(usize, Vec<&[u8]>)> = Vec::new();
+    let mut in_section = false;
+    for (i, line) in lines(input).enumerate() {
+        if line.trim_ascii().is_empty() {
+            in_section = false;
+            continue;
+        }
+        match sections.last_mut() {
+            Some((_, section)) if in_section => section.push(line),
+            _ => sections.push((i, Vec::from([line])))
+        }
+        in_section = true;
+    }
+    sections
+}
+
+pub fn words(line: &[u8]) -> impl Iterator<Item = &[u8]> {
+    line.split(u8::is_ascii_whitespace)
+        .filter(|word| !word.is_empty())
+}
+
+pub fn split_once(text: &[u8], separator: u8) -> Option<(&[u8], &[u8])> {
+    let i = text.iter().position(|byte| *byte == separator)?;
+    Some((&text[..i], &text[i + 1..]))
+}
+
+// A label and its ID up to a colon, like "Game 12:" or "Card   3:", and the rest of the line.
+pub fn labelled_id<'a>(line: &'a [u8], label: &str) -> Result<(u32, &'a [u8]), String> {
+    let (head, rest) = split_once(line, b':').ok_or_else(|| format!("no {} ID", label))?;
+    let id = head.trim_ascii()
+        .strip_prefix(label.as_bytes())
+        .filter(|id| id.first().is_some_and(u8::is_ascii_whitespace))
+        .ok_or_else(|| column_error(line, head.trim_ascii(),
+                                    format!("expected {} and an ID: {}", label, String::from_utf8_lossy(head))))?
+        .trim_ascii();
+    let id = parse_number(id)
+        .ok_or_else(|| column_error(line, id, format!("{} ID was not a number: {}", label, String::from_utf8_lossy(id))))?;
+    Ok((id, rest))
+}
+
+// What follows a named field, like the numbers after "seeds:".
+pub fn field<'a>(line: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    line.strip_prefix(name.as_bytes())?.strip_prefix(b":")
+}
+
+// The two words of an entry, in the order they are written.
+pub type KeyValue<'a> = (&'a [u8], &'a [u8]);
+
+// Entries of two words split on the separator, like "3 blue, 4 red". Blank entries are skipped.
+pub fn key_values(text: &[u8], separator: u8) -> Result<Vec<KeyValue<'_>>, String> {
+    text.split(|byte| *byte == separator)
+        .filter(|entry| !entry.trim_ascii().is_empty())
+        .map(|entry| {
+            let mut entry_words = words(entry);
+            match (entry_words.next(), entry_words.next(), entry_words.next()) {
+                (Some(key), Some(value), None) => Ok((key, value)),
+                _ => Err(column_error(text, entry.trim_ascii(),
+                                      format!("expected two words: {}", String::from_utf8_lossy(entry.trim_ascii()))))
+            }
+        })
+        .collect()
+}
+
+pub fn numbers<N: TryFrom<u64>>(text: &[u8]) -> Result<Vec<N>, String> {
+    words(text)
+        .map(|word| parse_number(word)
+            .ok_or_else(|| column_error(text, word, format!("not a number: {}", String::from_utf8_lossy(word)))))
+        .collect()
+}
+
+// The same numbers str::parse accepts, read straight from the bytes.
+pub fn parse_number<N: TryFrom<u64>>(digits: &[u8]) -> Option<N> {
+    let digits = digits.strip_prefix(b"+").unwrap_or(digits);
+    if digits.is_empty() {
+        return None
+    }
+    let number = digits.iter().try_fold(0_u64, |number, byte| {
+        if !byte.is_ascii_digit() {
+            return None
+        }
+        number.checked_mul(10)?.checked_add(u64::from(byte - b'0'))
+    })?;
+    N::try_from(number).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labelled_lines() {
+        assert_eq!(Ok((3, &b" 1 21 | 69 82"[..])), labelled_id(b"Card   3: 1 21 | 69 82", "Card"));
+        assert_eq!(Err(String::from("column 6: Game ID was not a number: x")), labelled_id(b"Game x: 3 blue", "Game"));
+        assert_eq!(Some(&b" 79 14"[..]), field(b"seeds: 79 14", "seeds"));
+        assert_eq!(vec![(&b"3"[..], &b"blue"[..]), (&b"4"[..], &b"red"[..])], key_values(b" 3 blue, 4 red", b',').unwrap());
+        assert_eq!(Err(String::from("column 9: expected two words: red")), key_values(b"3 blue, red", b','));
+    }
+
+    #[test]
+    fn error_columns() {
+        let line = b"Game 3: 1 red; 2 x";
+        let draw = &line[14..];
+        assert_eq!("column 18: not a number: x", column_error(line, draw, numbers::<u32>(draw).unwrap_err()));
+        assert_eq!("column 15: no draw", column_error(line, draw, String::from("no draw")));
+        assert_eq!("no draw", column_error(draw, line, String::from("no draw")));
+        assert_eq!(Err(String::from("column 1: expected Game and an ID: Gamer 1")), labelled_id(b"Gamer 1: 3 blue", "Game"));
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        assert_eq!(vec![(1, vec![&b"a"[..], &b"b"[..]]), (4, vec![&b"c"[..]])], sections(b"\na\nb\n \nc\n"));
+        assert_eq!(vec![(0, vec![&b"a"[..]]), (3, vec![&b"b"[..], &b"c"[..]])], sections(b"a\r\n\r\n\t\nb\nc"));
+        assert!(sections(b"\n \n").is_empty());
+    }
+
+    #[test]
+    fn numbers_and_positions() {
+        assert_eq!(Ok(vec![41_u32, 48, 6]), numbers(b" 41 48  6"));
+        assert_eq!(Err(String::from("column 3: not a number: 4294967296")), numbers::<u32>(b"1 4294967296"));
+        assert_eq!(Some(u64::MAX), parse_number(b"+18446744073709551615"));
+        assert_eq!(Err(String::from("line 2: column 1: not a number: x")), parse_lines(b"1 2\r\nx\n", numbers::<u32>));
+    }
+
+    #[test]
+    fn blank_lines() {
+        assert_eq!(Ok(vec![vec![1_u32], vec![2]]), parse_lines(b"1\n\n \t\r\n2\n\n", numbers));
+        assert_eq!(Err(String::from("line 4: column 3: not a number: x")), parse_lines(b"1\n\n\n1 x\n", numbers::<u32>));
+        assert_eq!(Ok(vec![vec![1_u32], vec![], vec![2]]), parse_rows(b"1\n\n2", numbers));
+    }
+}
@@ -0,0 +1,75 @@
+use wasm_bindgen::prelude::*;
+
+// Errors come back as strings, which wasm-bindgen throws on the JavaScript side. A panic still traps
+// the module, so the hook at least puts its message on the console, and the page stops solving.
+#[wasm_bindgen(start)]
+pub fn start() {
+    console_error_panic_hook::set_once();
+}
+
+#[wasm_bindgen]
+pub fn calibration_sum_actual(input: &str) -> Result<u64, String> {
+    day_01::parse(input.as_bytes()).map(|calibrations| day_01::part_2(&calibrations))
+}
+
+#[wasm_bindgen]
+pub fn power_sum(input: &str) -> Result<u128, String> {
+    day_02::parse(input.as_bytes()).map(|games| day_02::part_2(&games))
+}
+
+#[wasm_bindgen]
+pub fn schematic_gear_ratio_sum(input: &str) -> Result<u128, String> {
+    day_03::parse(input.as_bytes())
+        .and_then(|schematic| day_03::part_2(&schematic).ok_or_else(|| String::from("gear ratios too large to sum")))
+}
+
+#[wasm_bindgen]
+pub fn card_copies_sum(input: &str) -> Result<u128, String> {
+    day_04::parse(input.as_bytes())
+        .and_then(|card_wins| day_04::part_2(&card_wins).ok_or_else(|| String::from("too many copies to count")))
+}
+
+#[wasm_bindgen]
+pub fn lowest_location_number_actual(input: &str) -> Result<u64, String> {
+    day_05::parse(input.as_bytes())
+        .and_then(|almanac| day_05::part_2(&almanac).ok_or_else(|| String::from("no seed ranges")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    // `wasm-pack test --headless --firefox playground` runs these in a browser, and on other
+    // targets they run as plain tests.
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn examples() {
+        assert_eq!(Ok(281), calibration_sum_actual(include_str!("../../day_01/fixtures/example_2.txt")));
+        assert_eq!(Ok(48), power_sum("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"));
+        assert_eq!(Ok(16345), schematic_gear_ratio_sum("467..114..\n...*......\n..35..633."));
+        assert_eq!(Ok(3), card_copies_sum("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"));
+        assert_eq!(Ok(57), lowest_location_number_actual("seeds: 79 14 55 13\n\nseed-to-location map:\n50 98 2\n52 50 48\n"));
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn malformed_input() {
+        assert!(calibration_sum_actual("abc").is_err());
+        assert!(power_sum("Game x: 3 blue").is_err());
+        assert!(card_copies_sum("Card 1: 41 128 | 83").is_err());
+        assert_eq!(Err(String::from("no seed ranges")), lowest_location_number_actual("seeds: 79\n\nseed-to-location map:\n"));
+    }
+
+    // The page looks solvers up by name, so every section has to name one of the exports above.
+    #[wasm_bindgen_test(unsupported = test)]
+    fn page_solvers() {
+        let page = include_str!("../www/index.html");
+        let solvers: Vec<&str> = page.split("data-solver=\"").skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+        assert_eq!(vec!["calibration_sum_actual", "power_sum", "schematic_gear_ratio_sum", "card_copies_sum",
+                        "lowest_location_number_actual"], solvers);
+    }
+}
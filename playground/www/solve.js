@@ -0,0 +1,32 @@
+// Runs each section's solver on its input. Errors from Rust come back as thrown strings, but a panic
+// traps the module with a WebAssembly.RuntimeError and leaves it unusable, so nothing runs after one.
+// Returns whether a solver crashed.
+export function solveAll(sections, solvers) {
+  let crashed = false;
+  for (const section of sections) {
+    const input = section.querySelector("textarea").value;
+    const output = section.querySelector("output");
+    output.classList.remove("error");
+    if (input.trim() === "") {
+      output.textContent = "";
+      continue;
+    }
+    if (crashed) {
+      output.textContent = "not solved, an earlier solver crashed; reload the page to try again";
+      output.classList.add("error");
+      continue;
+    }
+    try {
+      output.textContent = String(solvers[section.dataset.solver](input.trimEnd()));
+    } catch (err) {
+      if (err instanceof WebAssembly.RuntimeError) {
+        crashed = true;
+        output.textContent = "the solver crashed, the console has the panic; reload the page to try again";
+      } else {
+        output.textContent = String(err);
+      }
+      output.classList.add("error");
+    }
+  }
+  return crashed;
+}
@@ -0,0 +1,39 @@
+// Run with `node --test playground/www`; the sections and solvers stand in for the page and the module.
+import assert from "node:assert/strict";
+import test from "node:test";
+import { solveAll } from "./solve.js";
+
+function section(solver, value) {
+  const classes = new Set();
+  const output = { textContent: "stale", classList: { add: (c) => classes.add(c), remove: (c) => classes.delete(c) } };
+  const textarea = { value };
+  return {
+    dataset: { solver },
+    querySelector: (selector) => (selector === "textarea" ? textarea : output),
+    output,
+    failed: () => classes.has("error")
+  };
+}
+
+const solvers = {
+  answer: (input) => input.length,
+  error: () => { throw "line 1: not a number: x"; },
+  panic: () => { throw new WebAssembly.RuntimeError("unreachable"); }
+};
+
+test("answers, errors and blank inputs", () => {
+  const sections = [section("answer", "abc\n"), section("error", "x"), section("answer", "  \n")];
+  assert.equal(solveAll(sections, solvers), false);
+  assert.equal(sections[0].output.textContent, "3");
+  assert.equal(sections[1].output.textContent, "line 1: not a number: x");
+  assert.ok(sections[1].failed());
+  assert.equal(sections[2].output.textContent, "");
+});
+
+test("a panic stops the later solvers", () => {
+  const sections = [section("panic", "1"), section("answer", "abc")];
+  assert.equal(solveAll(sections, solvers), true);
+  assert.match(sections[0].output.textContent, /crashed/);
+  assert.match(sections[1].output.textContent, /not solved/);
+  assert.ok(sections[1].failed());
+});
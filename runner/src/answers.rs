@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+// Answers are stored exactly as a day prints them, one "Part N: answer" line per part.
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+    parts: BTreeMap<String, String>
+}
+
+impl Answers {
+    pub fn parse(output: &str) -> Self {
+        let parts = output.lines()
+            .filter_map(|line| line.split_once(": "))
+            .filter(|(part, _)| part.starts_with("Part "))
+            .map(|(part, answer)| (String::from(part), String::from(answer.trim())))
+            .collect();
+        Self { parts }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    pub fn mismatches(&self, actual: &Answers) -> Vec<String> {
+        self.parts.iter()
+            .filter_map(|(part, expected)| match actual.parts.get(part) {
+                None => Some(format!("{}: expected {}, but no answer was given", part, expected)),
+                Some(answer) if answer != expected => Some(format!("{}: expected {}, got {}", part, expected, answer)),
+                Some(_) => None
+            })
+            .collect()
+    }
+
+    pub fn unrecorded<'a>(&'a self, actual: &'a Answers) -> Vec<&'a str> {
+        actual.parts.keys()
+            .filter(|part| !self.parts.contains_key(*part))
+            .map(|part| part.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatches() {
+        let expected = Answers::parse("Part 1: 142\nPart 2: 281\n");
+        assert!(expected.mismatches(&Answers::parse("Part 1: 142\nPart 2: 281")).is_empty());
+        assert_eq!(vec!["Part 2: expected 281, got 280"],
+                   expected.mismatches(&Answers::parse("Part 1: 142\nPart 2: 280\n")));
+        assert_eq!(vec!["Part 2: expected 281, but no answer was given"],
+                   expected.mismatches(&Answers::parse("Part 1: 142\n")));
+    }
+
+    #[test]
+    fn unrecorded() {
+        let expected = Answers::parse("Part 1: 35\n");
+        assert_eq!(vec!["Part 2"], expected.unrecorded(&Answers::parse("Part 1: 35\nPart 2: 46\n")));
+    }
+}
@@ -0,0 +1,136 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Where inputs missing from the store come from. Nothing is fetched unless a backend is configured,
+// and tests swap in their own.
+pub trait Fetch {
+    fn fetch(&self, year: u32, day: u32) -> Result<Vec<u8>, String>;
+}
+
+// Asks an HTTP server for <url>/<year>/day/<day>/input, sending the session cookie if there is one.
+// It only speaks plain HTTP/1.0, so the body never comes back chunked, and as nothing is encrypted
+// the session cookie is only ever sent to a server on this machine.
+pub struct HttpFetch {
+    host: String,
+    prefix: String,
+    session: Option<String>
+}
+
+impl HttpFetch {
+    pub fn new(url: &str, session: Option<String>) -> Result<Self, String> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| format!("only http:// urls can be fetched from: {}", url))?;
+        let (host, prefix) = rest.find('/').map_or((rest, ""), |i| rest.split_at(i));
+        if host.is_empty() {
+            return Err(format!("no host in url: {}", url))
+        }
+        let host = if host.contains(':') { String::from(host) } else { format!("{}:80", host) };
+        // The session goes straight into a header, so anything that could end the header or the cookie is refused.
+        if session.as_deref().is_some_and(|session| session.chars().any(|c| c.is_ascii_control() || c == ';')) {
+            return Err(String::from("the session cookie can't contain control characters or ;"))
+        }
+        Ok(Self { host, prefix: String::from(prefix.trim_end_matches('/')), session })
+    }
+}
+
+impl Fetch for HttpFetch {
+    fn fetch(&self, year: u32, day: u32) -> Result<Vec<u8>, String> {
+        let addrs: Vec<SocketAddr> = self.host.to_socket_addrs()
+            .map_err(|err| format!("Could not resolve {}: {}", self.host, err))?
+            .collect();
+        if self.session.is_some() && !addrs.iter().all(|addr| addr.ip().is_loopback()) {
+            return Err(format!("not sending the session cookie over plain http to {}, which isn't loopback", self.host))
+        }
+        let mut stream = connect(&addrs).map_err(|err| format!("Could not connect to {}: {}", self.host, err))?;
+        stream.set_read_timeout(Some(IO_TIMEOUT)).map_err(|err| err.to_string())?;
+        stream.set_write_timeout(Some(IO_TIMEOUT)).map_err(|err| err.to_string())?;
+        let mut request = format!("GET {}/{}/day/{}/input HTTP/1.0\r\nHost: {}\r\n", self.prefix, year, day, self.host);
+        if let Some(session) = &self.session {
+            request += &format!("Cookie: session={}\r\n", session);
+        }
+        request += "\r\n";
+        stream.write_all(request.as_bytes()).map_err(|err| err.to_string())?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(|err| err.to_string())?;
+        let header_end = response.windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .ok_or("malformed response")?;
+        let head = String::from_utf8_lossy(&response[..header_end]);
+        let status = head.lines().next().unwrap_or("");
+        if status.split_whitespace().nth(1) != Some("200") {
+            return Err(format!("{} answered {}", self.host, status))
+        }
+        Ok(response[(header_end + 4)..].to_vec())
+    }
+}
+
+fn connect(addrs: &[SocketAddr]) -> Result<TcpStream, String> {
+    let mut last_err = String::from("no addresses");
+    for addr in addrs {
+        match TcpStream::connect_timeout(addr, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = err.to_string()
+        }
+    }
+    Err(last_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+    use super::*;
+
+    // Answers a single request with the given response, handing back the request line and headers.
+    fn stub_server(response: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/aoc/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request: Vec<String> = BufReader::new(&stream).lines()
+                .map(|line| line.unwrap())
+                .take_while(|line| !line.is_empty())
+                .collect();
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, server) = stub_server("HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\n\r\nseeds: 79 14\n");
+        let input = HttpFetch::new(&url, Some(String::from("abc"))).unwrap().fetch(2023, 5).unwrap();
+        assert_eq!(b"seeds: 79 14\n".to_vec(), input);
+        let request = server.join().unwrap();
+        assert_eq!("GET /aoc/2023/day/5/input HTTP/1.0", request[0]);
+        assert!(request.contains(&String::from("Cookie: session=abc")));
+    }
+
+    #[test]
+    fn error_status() {
+        let (url, server) = stub_server("HTTP/1.0 404 Not Found\r\n\r\n");
+        assert_eq!(Err(format!("{} answered HTTP/1.0 404 Not Found", &url[7..url.len() - 5])),
+                   HttpFetch::new(&url, None).unwrap().fetch(2023, 26));
+        server.join().unwrap();
+        assert!(HttpFetch::new("https://adventofcode.com", None).is_err());
+    }
+
+    #[test]
+    fn session_checked() {
+        assert!(HttpFetch::new("http://127.0.0.1/", Some(String::from("abc\r\nX-Injected: 1"))).is_err());
+        assert!(HttpFetch::new("http://127.0.0.1/", Some(String::from("abc; admin=1"))).is_err());
+        assert!(HttpFetch::new("http://127.0.0.1/", Some(String::from("53616c746564"))).is_ok());
+    }
+
+    #[test]
+    fn session_stays_local() {
+        let fetch = HttpFetch::new("http://192.0.2.1/", Some(String::from("abc"))).unwrap();
+        assert_eq!(Err(String::from("not sending the session cookie over plain http to 192.0.2.1:80, which isn't loopback")),
+                   fetch.fetch(2023, 5));
+    }
+}
@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::PathBuf;
+use cli::checksum;
+use crate::fetch::Fetch;
+
+// Puzzle inputs kept by (year, day, account). Each is stored as <year>/<account>/day_NN.txt under the store,
+// and index.txt records one "year day account checksum" line per input.
+pub struct Store {
+    dir: PathBuf
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub account: String,
+    pub checksum: u64
+}
+
+impl Store {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn entries(&self) -> Result<Vec<Entry>, String> {
+        let index = match fs::read_to_string(self.dir.join("index.txt")) {
+            Ok(index) => index,
+            Err(_) => return Ok(Vec::new())
+        };
+        index.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_entry)
+            .collect()
+    }
+
+    pub fn import(&self, year: u32, day: u32, account: &str, input: &[u8]) -> Result<Entry, String> {
+        check_account(account)?;
+        let path = self.path(year, day, account);
+        fs::create_dir_all(path.parent().expect("input path has no directory")).map_err(|err| err.to_string())?;
+        fs::write(&path, input).map_err(|err| format!("Could not write {}: {}", path.display(), err))?;
+        let entry = Entry { year, day, account: String::from(account), checksum: checksum(input) };
+        let mut entries = self.entries()?;
+        entries.retain(|other| (other.year, other.day, &other.account) != (year, day, &entry.account));
+        entries.push(entry.clone());
+        entries.sort_by(|a, b| (a.year, a.day, &a.account).cmp(&(b.year, b.day, &b.account)));
+        let index: String = entries.iter()
+            .map(|entry| format!("{} {} {} {:016x}\n", entry.year, entry.day, entry.account, entry.checksum))
+            .collect();
+        fs::write(self.dir.join("index.txt"), index).map_err(|err| err.to_string())?;
+        Ok(entry)
+    }
+
+    pub fn find(&self, year: u32, day: u32, account: &str) -> Result<Entry, String> {
+        self.entries()?
+            .into_iter()
+            .find(|entry| (entry.year, entry.day, entry.account.as_str()) == (year, day, account))
+            .ok_or_else(|| format!("no input stored for {} day {} ({})", year, day, account))
+    }
+
+    // Only inputs the store doesn't have yet are fetched, so once an input is imported everything works offline.
+    pub fn fetch(&self, fetcher: &dyn Fetch, year: u32, day: u32, account: &str) -> Result<Entry, String> {
+        if let Ok(entry) = self.find(year, day, account) {
+            return Ok(entry)
+        }
+        self.import(year, day, account, &fetcher.fetch(year, day)?)
+    }
+
+    // Reads an input back, refusing it if it no longer matches the checksum it was imported with.
+    pub fn read(&self, entry: &Entry) -> Result<Vec<u8>, String> {
+        let path = self.path_of(entry);
+        let input = fs::read(&path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        if checksum(&input) != entry.checksum {
+            return Err(format!("{} has changed since it was imported", path.display()))
+        }
+        Ok(input)
+    }
+
+    pub fn holds(&self, input: &[u8]) -> Result<bool, String> {
+        let checksum = checksum(input);
+        Ok(self.entries()?.iter().any(|entry| entry.checksum == checksum))
+    }
+
+    pub fn path_of(&self, entry: &Entry) -> PathBuf {
+        self.path(entry.year, entry.day, &entry.account)
+    }
+
+    fn path(&self, year: u32, day: u32, account: &str) -> PathBuf {
+        self.dir.join(year.to_string()).join(account).join(format!("day_{:02}.txt", day))
+    }
+}
+
+fn parse_entry(line: &str) -> Result<Entry, String> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let [year, day, account, checksum] = parts[..] else {
+        return Err(format!("index line should have four fields: {}", line))
+    };
+    Ok(Entry {
+        year: year.parse().map_err(|_| format!("year was not a number: {}", year))?,
+        day: day.parse().map_err(|_| format!("day was not a number: {}", day))?,
+        account: String::from(account),
+        checksum: u64::from_str_radix(checksum, 16).map_err(|_| format!("checksum was not hexadecimal: {}", checksum))?
+    })
+}
+
+// Accounts name a directory in the store, so they are kept to plain labels.
+fn check_account(account: &str) -> Result<(), String> {
+    if account.is_empty() || !account.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("account labels can only use letters, digits, - and _: {}", account))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::env;
+    use super::*;
+
+    // Removes the store's directory once a test is done with it, even if the test fails.
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn store(name: &str) -> (Store, TempDir) {
+        let dir = env::temp_dir().join(format!("runner_inputs_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        (Store::new(dir.clone()), TempDir(dir))
+    }
+
+    #[test]
+    fn import_and_read() {
+        let (store, _dir) = store("import");
+        store.import(2023, 5, "work", b"seeds: 1 2\n").unwrap();
+        store.import(2023, 1, "home", b"1abc2\n").unwrap();
+        let entry = store.import(2023, 5, "work", b"seeds: 3 4\n").unwrap();
+        assert_eq!(vec![(1, "home"), (5, "work")], store.entries().unwrap().iter()
+            .map(|entry| (entry.day, entry.account.as_str()))
+            .collect::<Vec<_>>());
+        assert_eq!(entry, store.find(2023, 5, "work").unwrap());
+        assert_eq!(b"seeds: 3 4\n".to_vec(), store.read(&entry).unwrap());
+        assert!(store.find(2023, 5, "home").is_err());
+        assert!(store.import(2023, 5, "../work", b"").is_err());
+        assert!(store.holds(b"1abc2\n").unwrap());
+        assert!(!store.holds(b"seeds: 1 2\n").unwrap());
+    }
+
+    #[test]
+    fn fetch_once() {
+        struct Stub(Cell<u32>);
+        impl Fetch for Stub {
+            fn fetch(&self, _: u32, day: u32) -> Result<Vec<u8>, String> {
+                self.0.set(self.0.get() + 1);
+                Ok(format!("input for day {}\n", day).into_bytes())
+            }
+        }
+        let (store, _dir) = store("fetch");
+        let stub = Stub(Cell::new(0));
+        let entry = store.fetch(&stub, 2023, 3, "work").unwrap();
+        assert_eq!(entry, store.fetch(&stub, 2023, 3, "work").unwrap());
+        assert_eq!(1, stub.0.get());
+        assert_eq!(b"input for day 3\n".to_vec(), store.read(&entry).unwrap());
+    }
+
+    #[test]
+    fn changed_input() {
+        let (store, _dir) = store("changed");
+        let entry = store.import(2023, 2, "work", b"Game 1: 3 blue\n").unwrap();
+        fs::write(store.path(2023, 2, "work"), b"Game 1: 4 blue\n").unwrap();
+        assert!(store.read(&entry).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+mod answers;
+mod fetch;
+mod inputs;
+mod scaffold;
+
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::{env, fs, thread};
+use answers::Answers;
+use fetch::HttpFetch;
+use inputs::{Entry, Store};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let json = args.iter().any(|arg| arg == "--json");
+    let parallel = args.iter().any(|arg| arg == "--parallel");
+    let day_args: Vec<String> = args.iter().skip(2).filter(|arg| !arg.starts_with("--")).cloned().collect();
+    let days = days(&day_args);
+    let success = match args.get(1).map(|arg| arg.as_str()) {
+        Some("run") => run(&days, json, parallel),
+        Some("verify") => verify(&days, parallel),
+        Some("record") => record(&days, parallel),
+        Some("no-std") => check_no_std(),
+        Some("inputs") => inputs(&args[2..]),
+        Some("new-day") => new_day(&args[2..]),
+        _ => {
+            eprintln!("usage: runner <run|verify|record> [day...] [--json] [--parallel]");
+            eprintln!("       runner no-std");
+            eprintln!("       runner new-day DAY");
+            eprintln!("       runner inputs <list|verify>");
+            eprintln!("       runner inputs <import DAY PATH|fetch DAY|select DAY> [--year YEAR] [--account LABEL]");
+            false
+        }
+    };
+    if !success {
+        process::exit(1);
+    }
+}
+
+// With --json every day prints one JSON object per part, so the combined output is JSON Lines.
+fn run(days: &[String], json: bool, parallel: bool) -> bool {
+    let args: &[&str] = if json { &["--json"] } else { &[] };
+    let mut success = true;
+    for (day, output) in days.iter().zip(run_days(days, args, parallel)) {
+        match output {
+            Ok(output) if json => print!("{}", output),
+            Ok(output) => print!("{}\n{}", day, output),
+            Err(err) => {
+                eprintln!("{}: failed to run\n{}", day, err);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+fn verify(days: &[String], parallel: bool) -> bool {
+    let mut success = true;
+    for (day, output) in days.iter().zip(run_days(days, &[], parallel)) {
+        let expected = match fs::read_to_string(answers_path(day)) {
+            Ok(answers) => Answers::parse(&answers),
+            Err(_) => {
+                println!("{}: no answers recorded", day);
+                success = false;
+                continue;
+            }
+        };
+        let actual = match output {
+            Ok(output) => Answers::parse(&output),
+            Err(err) => {
+                println!("{}: failed to run\n{}", day, err);
+                success = false;
+                continue;
+            }
+        };
+        let mismatches = expected.mismatches(&actual);
+        if mismatches.is_empty() {
+            println!("{}: ok", day);
+        }
+        for mismatch in &mismatches {
+            println!("{}: {}", day, mismatch);
+        }
+        for part in expected.unrecorded(&actual) {
+            println!("{}: {} has no recorded answer", day, part);
+        }
+        success &= mismatches.is_empty();
+    }
+    success
+}
+
+fn record(days: &[String], parallel: bool) -> bool {
+    let mut success = true;
+    for (day, output) in days.iter().zip(run_days(days, &[], parallel)) {
+        match output {
+            Ok(output) if !Answers::parse(&output).is_empty() => {
+                fs::write(answers_path(day), &output).expect("Could not write answers");
+                print!("{}: recorded\n{}", day, output);
+            },
+            Ok(_) => {
+                println!("{}: no answers were given", day);
+                success = false;
+            },
+            Err(err) => {
+                println!("{}: failed to run\n{}", day, err);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+// no_std_check is its own workspace so that features enabled here can't unify std into it. It is
+// built for a target without std, so anything that still needs std fails to build.
+const NO_STD_TARGET: &str = "thumbv7em-none-eabi";
+
+fn check_no_std() -> bool {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let status = Command::new(cargo)
+        .args(["build", "--quiet", "--target", NO_STD_TARGET])
+        .current_dir(root().join("no_std_check"))
+        .status()
+        .expect("Could not run cargo");
+    println!("no_std core: {}", if status.success() { "ok" } else { "failed" });
+    if !status.success() {
+        eprintln!("the {} target may be missing: rustup target add {}", NO_STD_TARGET, NO_STD_TARGET);
+    }
+    status.success()
+}
+
+fn new_day(args: &[String]) -> bool {
+    let result = match args {
+        [day] => day_number(day).and_then(|day| scaffold::new_day(root(), day)),
+        _ => Err(String::from("usage: runner new-day DAY"))
+    };
+    match result {
+        Ok(name) => {
+            println!("created {}", name);
+            true
+        },
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+const DEFAULT_YEAR: u32 = 2023;
+
+// The store is inputs/ at the top of the repository, unless RUNNER_INPUTS points at another directory,
+// such as one synced between machines.
+fn inputs(args: &[String]) -> bool {
+    let store = Store::new(env::var_os("RUNNER_INPUTS").map_or_else(|| root().join("inputs"), PathBuf::from));
+    let account = option(args, "--account").unwrap_or("default");
+    let result = option(args, "--year")
+        .map_or(Ok(DEFAULT_YEAR), |year| year.parse().map_err(|_| format!("year was not a number: {}", year)))
+        .and_then(|year| match positional(args)[..] {
+            ["list"] => list_inputs(&store),
+            ["verify"] => verify_inputs(&store),
+            ["import", day, path] => {
+                let input = fs::read(path).map_err(|err| format!("Could not read {}: {}", path, err))?;
+                let entry = store.import(year, day_number(day)?, account, &input)?;
+                println!("{}: imported {:016x}", label(&entry), entry.checksum);
+                Ok(true)
+            },
+            ["fetch", day] => {
+                let entry = store.fetch(&configured_fetch()?, year, day_number(day)?, account)?;
+                println!("{}: stored {:016x}", label(&entry), entry.checksum);
+                Ok(true)
+            },
+            ["select", day] => select_input(&store, year, day_number(day)?, account),
+            _ => Err(String::from("usage: runner inputs <list|verify|import DAY PATH|fetch DAY|select DAY> \
+                                   [--year YEAR] [--account LABEL]"))
+        });
+    result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        false
+    })
+}
+
+fn list_inputs(store: &Store) -> Result<bool, String> {
+    for entry in store.entries()? {
+        let active = active_checksum(entry.day) == Some(entry.checksum);
+        println!("{:<24} {:016x}{}", label(&entry), entry.checksum, if active { "  active" } else { "" });
+    }
+    Ok(true)
+}
+
+fn verify_inputs(store: &Store) -> Result<bool, String> {
+    let entries = store.entries()?;
+    let mut success = true;
+    for entry in &entries {
+        match store.read(entry) {
+            Ok(_) => println!("{}: ok", label(entry)),
+            Err(err) => {
+                println!("{}: {}", label(entry), err);
+                success = false;
+            }
+        }
+    }
+    for day in days(&[]) {
+        let Some(checksum) = day_number(&day).ok().and_then(active_checksum) else {
+            continue;
+        };
+        match entries.iter().find(|entry| entry.checksum == checksum) {
+            Some(entry) => println!("{}/input.txt: {}", day, label(entry)),
+            None => println!("{}/input.txt: not in the store", day)
+        }
+    }
+    Ok(success)
+}
+
+fn select_input(store: &Store, year: u32, day: u32, account: &str) -> Result<bool, String> {
+    let day_dir = root().join(format!("day_{:02}", day));
+    if !day_dir.join("Cargo.toml").exists() {
+        return Err(format!("there is no day_{:02} crate", day))
+    }
+    let entry = store.find(year, day, account)?;
+    let active = day_dir.join("input.txt");
+    // The active input is only replaced when that loses nothing: git doesn't track it and the store already has a copy.
+    if let Ok(current) = fs::read(&active) {
+        if tracked(&active) {
+            return Err(format!("day_{:02}/input.txt is tracked by git, so it isn't replaced; run day_{:02} --input {} instead",
+                               day, day, store.path_of(&entry).display()))
+        }
+        if !store.holds(&current)? {
+            return Err(format!("day_{:02}/input.txt isn't in the store, import it before selecting another input", day))
+        }
+    }
+    fs::write(&active, store.read(&entry)?).map_err(|err| err.to_string())?;
+    println!("day_{:02}/input.txt: {}", day, label(&entry));
+    Ok(true)
+}
+
+// Fetching is off unless RUNNER_FETCH_URL names a server; RUNNER_SESSION is sent as the session cookie, to loopback servers only.
+fn configured_fetch() -> Result<HttpFetch, String> {
+    let url = env::var("RUNNER_FETCH_URL")
+        .map_err(|_| String::from("no fetch backend is configured, set RUNNER_FETCH_URL"))?;
+    HttpFetch::new(&url, env::var("RUNNER_SESSION").ok())
+}
+
+// Without git nothing counts as tracked.
+fn tracked(path: &Path) -> bool {
+    Command::new("git")
+        .args(["ls-files", "--error-unmatch", "--"])
+        .arg(path)
+        .current_dir(root())
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn active_checksum(day: u32) -> Option<u64> {
+    fs::read(root().join(format!("day_{:02}", day)).join("input.txt")).ok()
+        .map(|input| cli::checksum(&input))
+}
+
+fn label(entry: &Entry) -> String {
+    format!("{} day {} ({})", entry.year, entry.day, entry.account)
+}
+
+fn day_number(day: &str) -> Result<u32, String> {
+    day.strip_prefix("day_").unwrap_or(day).parse().map_err(|_| format!("not a day: {}", day))
+}
+
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|arg| arg.as_str())
+}
+
+// The arguments left once options and their values are taken out.
+fn positional(args: &[String]) -> Vec<&str> {
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--") {
+            args.next();
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
+}
+
+// With --parallel the days run concurrently and are built with their parallel feature,
+// but the outputs still come back in the order the days were given.
+fn run_days(days: &[String], args: &[&str], parallel: bool) -> Vec<Result<String, String>> {
+    if !parallel {
+        return days.iter()
+            .map(|day| run_day(day, args, false))
+            .collect();
+    }
+    thread::scope(|scope| {
+        let handles: Vec<_> = days.iter()
+            .map(|day| scope.spawn(move || run_day(day, args, true)))
+            .collect();
+        handles.into_iter()
+            .map(|handle| handle.join().expect("day runner panicked"))
+            .collect()
+    })
+}
+
+fn run_day(day: &str, args: &[&str], parallel: bool) -> Result<String, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let features: &[&str] = if parallel { &["--features", "parallel"] } else { &[] };
+    let output = Command::new(cargo)
+        .args(["run", "--release", "--quiet", "--package", day])
+        .args(features)
+        .arg("--")
+        .args(args)
+        .current_dir(root().join(day))
+        .output()
+        .map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    String::from_utf8(output.stdout).map_err(|err| err.to_string())
+}
+
+fn days(args: &[String]) -> Vec<String> {
+    if !args.is_empty() {
+        return args.iter()
+            .map(|arg| match arg.parse::<u32>() {
+                Ok(day) => format!("day_{:02}", day),
+                Err(_) => arg.clone()
+            })
+            .collect();
+    }
+    let mut days: Vec<String> = fs::read_dir(root()).expect("Could not read repository")
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("day_") && root().join(name).join("Cargo.toml").exists())
+        .collect();
+    days.sort();
+    days
+}
+
+fn answers_path(day: &str) -> PathBuf {
+    root().join(day).join("answers.txt")
+}
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("runner is not in the repository")
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::Path;
+
+const CARGO_TOML: &str = include_str!("../templates/day/Cargo.toml.template");
+const LIB_RS: &str = include_str!("../templates/day/lib.rs.template");
+const MAIN_RS: &str = include_str!("../templates/day/main.rs.template");
+
+// Writes the new crate first and only then registers it, so a failed scaffold never leaves
+// the workspace or no_std_check pointing at a crate that isn't there.
+pub fn new_day(root: &Path, day: u32) -> Result<String, String> {
+    let name = format!("day_{:02}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", name))
+    }
+    let workspace = add_member(&read(&root.join("Cargo.toml"))?, &name)?;
+    let no_std_manifest = add_dependency(&read(&root.join("no_std_check/Cargo.toml"))?, &name)?;
+    let no_std_lib = add_reexport(&read(&root.join("no_std_check/src/lib.rs"))?, &name)?;
+    for (path, contents) in [
+        ("Cargo.toml", render(CARGO_TOML, &name, day)),
+        ("src/lib.rs", render(LIB_RS, &name, day)),
+        ("src/main.rs", render(MAIN_RS, &name, day)),
+        ("fixtures/example_1.txt", String::new())
+    ] {
+        write(&dir.join(path), &contents)?;
+    }
+    write(&root.join("Cargo.toml"), &workspace)?;
+    write(&root.join("no_std_check/Cargo.toml"), &no_std_manifest)?;
+    write(&root.join("no_std_check/src/lib.rs"), &no_std_lib)?;
+    Ok(name)
+}
+
+fn render(template: &str, name: &str, day: u32) -> String {
+    template.replace("{{crate}}", name).replace("{{day}}", &day.to_string())
+}
+
+// The days stay in order in the members list, ahead of the tool crates.
+fn add_member(manifest: &str, name: &str) -> Result<String, String> {
+    let (before, rest) = manifest.split_once("members = [").ok_or("no workspace members in Cargo.toml")?;
+    let (members, after) = rest.split_once(']').ok_or("unterminated workspace members in Cargo.toml")?;
+    let mut members: Vec<String> = members.split(',')
+        .map(|member| member.trim().trim_matches('"').to_string())
+        .filter(|member| !member.is_empty())
+        .collect();
+    let position = members.iter()
+        .position(|member| !member.starts_with("day_") || member.as_str() > name)
+        .unwrap_or(members.len());
+    members.insert(position, name.to_string());
+    let members = members.iter()
+        .map(|member| format!("\"{}\"", member))
+        .collect::<Vec<String>>()
+        .join(", ");
+    Ok(format!("{}members = [{}]{}", before, members, after))
+}
+
+fn add_dependency(manifest: &str, name: &str) -> Result<String, String> {
+    insert_after_last(manifest, "day_", &format!("{} = {{ path = \"../{}\" }}\n", name, name))
+        .ok_or_else(|| String::from("no day dependencies in no_std_check/Cargo.toml"))
+}
+
+fn add_reexport(lib: &str, name: &str) -> Result<String, String> {
+    insert_after_last(lib, "pub use day_", &format!("pub use {};\n", name))
+        .ok_or_else(|| String::from("no day re-exports in no_std_check/src/lib.rs"))
+}
+
+fn insert_after_last(text: &str, prefix: &str, line: &str) -> Option<String> {
+    let mut lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let last = lines.iter().rposition(|existing| existing.starts_with(prefix))?;
+    lines.insert(last + 1, line);
+    Some(lines.concat())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("Could not create {}: {}", parent.display(), err))?;
+    }
+    fs::write(path, contents).map_err(|err| format!("Could not write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_day() {
+        let manifest = "[workspace]\nmembers = [\"day_01\", \"day_05\", \"explorer\"]\nresolver = \"2\"\n";
+        assert_eq!("[workspace]\nmembers = [\"day_01\", \"day_05\", \"day_06\", \"explorer\"]\nresolver = \"2\"\n",
+                   add_member(manifest, "day_06").unwrap());
+        let lib = "use core::panic::PanicInfo;\n\npub use day_01;\npub use day_05;\n\n#[panic_handler]\n";
+        assert_eq!("use core::panic::PanicInfo;\n\npub use day_01;\npub use day_05;\npub use day_06;\n\n#[panic_handler]\n",
+                   add_reexport(lib, "day_06").unwrap());
+    }
+
+    #[test]
+    fn renders_template() {
+        let main = render(MAIN_RS, "day_06", 6);
+        assert!(main.contains("use day_06::{parse, part_1, part_2};"));
+        assert!(main.contains("print_part(6, json, 1,"));
+        assert!(!render(CARGO_TOML, "day_06", 6).contains("{{"));
+    }
+}